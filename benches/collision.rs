@@ -0,0 +1,235 @@
+//! Comparación de la fase amplia con rejilla frente al bucle anidado de todos los pares,
+//! y coste de la fase estrecha con hitboxes sobre la rejilla
+//! (`hits`: pares que chocan con rectángulos / con hitboxes)
+//!
+//! `cargo bench --bench collision`
+
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::collide;
+use juego::collision::{layer, CollisionGrid, Hitbox, Shape, WorldShape};
+use juego::components::SpriteSize;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const PLAYFIELD: (f32, f32) = (1920., 1080.);
+const CELL_SIZE: f32 = 120.;
+const SPRITE_SCALE: f32 = 0.5;
+// tamaños y hitboxes en píxeles del sprite, como en el juego
+const ENEMY_SPRITE_SIZE: Vec2 = Vec2::new(93., 84.);
+const ENEMY_HULL: [(f32, f32); 6] = [
+    (-30., 40.),
+    (30., 40.),
+    (47., 14.),
+    (26., -42.),
+    (-26., -42.),
+    (-47., 14.),
+];
+const LASER_SPRITE_SIZE: Vec2 = Vec2::new(9., 54.);
+const LASER_ANGLE: f32 = 0.6; // inclinación máxima de los lasers, como en los abanicos
+const ITERATIONS: u32 = 50;
+
+struct Body {
+    entity: Entity,
+    center: Vec2,
+    size: Vec2, // rectángulo sin girar de las pruebas de rejilla
+    transform: Transform,
+    sprite_size: SpriteSize,
+    hitbox: Hitbox,
+}
+
+// enemigos en rejilla en la mitad superior y lasers repartidos por toda la pantalla
+fn scene(enemies: usize, lasers: usize) -> (Vec<Body>, Vec<Body>) {
+    let mut rng = StdRng::seed_from_u64(42);
+    let half = Vec2::from(PLAYFIELD) / 2.;
+
+    let enemies = (0..enemies)
+        .map(|i| {
+            let center = Vec2::new(rng.gen_range(-half.x..half.x), rng.gen_range(0.0..half.y));
+            Body::new(
+                i,
+                center,
+                0.,
+                ENEMY_SPRITE_SIZE,
+                Shape::Polygon(ENEMY_HULL.to_vec()),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let lasers = (0..lasers)
+        .map(|i| {
+            let center = Vec2::new(rng.gen_range(-half.x..half.x), rng.gen_range(-half.y..half.y));
+            let angle = rng.gen_range(-LASER_ANGLE..LASER_ANGLE);
+            Body::new(
+                enemies.len() + i,
+                center,
+                angle,
+                LASER_SPRITE_SIZE,
+                Shape::Capsule {
+                    a: (0., -22.5),
+                    b: (0., 22.5),
+                    radius: 4.5,
+                },
+            )
+        })
+        .collect();
+
+    (enemies, lasers)
+}
+
+impl Body {
+    fn new(index: usize, center: Vec2, angle: f32, sprite_size: Vec2, shape: Shape) -> Self {
+        Self {
+            entity: Entity::from_raw(index as u32),
+            center,
+            size: sprite_size * SPRITE_SCALE,
+            transform: Transform {
+                translation: center.extend(0.),
+                rotation: Quat::from_rotation_z(angle),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+            },
+            sprite_size: SpriteSize(sprite_size),
+            hitbox: Hitbox(shape),
+        }
+    }
+
+    fn shape(&self, shape: &mut WorldShape) {
+        shape.set(&self.transform, &self.sprite_size, Some(&self.hitbox));
+    }
+
+    fn rect(&self) -> Rect {
+        Rect::from_center_size(self.center, self.size)
+    }
+}
+
+fn hit(a: &Body, b: &Body) -> bool {
+    collide(a.center.extend(0.), a.size, b.center.extend(0.), b.size).is_some()
+}
+
+fn naive(enemies: &[Body], lasers: &[Body]) -> usize {
+    let mut hits = 0;
+    for laser in lasers {
+        for enemy in enemies {
+            if hit(laser, enemy) {
+                hits += 1;
+            }
+        }
+    }
+    hits
+}
+
+// incluye el coste de rellenar la rejilla, como ocurre en cada tick
+fn grid(grid: &mut CollisionGrid, enemies: &[Body], lasers: &[Body]) -> usize {
+    grid.clear();
+    for enemy in enemies {
+        grid.insert(enemy.entity, layer::ENEMY, enemy.rect());
+    }
+    for laser in lasers {
+        grid.insert(laser.entity, layer::PLAYER_LASER, laser.rect());
+    }
+
+    // como en `collision_system`, solo los enemigos tienen máscara y piden lasers a la rejilla
+    let mut hits = 0;
+    for enemy in enemies {
+        for (candidate, _) in grid.query(enemy.rect(), layer::PLAYER_LASER) {
+            // los índices de los lasers van detrás de los de los enemigos
+            let laser = &lasers[candidate.index() as usize - enemies.len()];
+            if hit(laser, enemy) {
+                hits += 1;
+            }
+        }
+    }
+    hits
+}
+
+// como `collision_grid_system` y `collision_system`: la rejilla guarda las cajas de las
+// hitboxes y cada par candidato pasa por la fase estrecha reutilizando las dos formas
+fn narrow(
+    grid: &mut CollisionGrid,
+    (shape, other): &mut (WorldShape, WorldShape),
+    enemies: &[Body],
+    lasers: &[Body],
+) -> usize {
+    grid.clear();
+    for (bodies, layer) in [(enemies, layer::ENEMY), (lasers, layer::PLAYER_LASER)] {
+        for body in bodies {
+            body.shape(shape);
+            grid.insert(body.entity, layer, shape.bounds());
+        }
+    }
+
+    let mut hits = 0;
+    for enemy in enemies {
+        enemy.shape(shape);
+        for (candidate, _) in grid.query(shape.bounds(), layer::PLAYER_LASER) {
+            lasers[candidate.index() as usize - enemies.len()].shape(other);
+            if shape.overlaps(other) {
+                hits += 1;
+            }
+        }
+    }
+    hits
+}
+
+// fase estrecha sobre todos los pares, solo para comprobar el resultado de `narrow`
+fn narrow_all_pairs(enemies: &[Body], lasers: &[Body]) -> usize {
+    let (mut shape, mut other) = (WorldShape::default(), WorldShape::default());
+    let mut hits = 0;
+    for enemy in enemies {
+        enemy.shape(&mut shape);
+        for laser in lasers {
+            laser.shape(&mut other);
+            if shape.overlaps(&other) {
+                hits += 1;
+            }
+        }
+    }
+    hits
+}
+
+fn measure(mut f: impl FnMut() -> usize) -> (Duration, usize) {
+    let hits = f();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    (start.elapsed() / ITERATIONS, hits)
+}
+
+fn main() {
+    let bounds = Rect::from_center_size(Vec2::ZERO, Vec2::from(PLAYFIELD));
+    let mut collision_grid = CollisionGrid::new(bounds, CELL_SIZE);
+    let mut shapes = (WorldShape::default(), WorldShape::default());
+
+    println!(
+        "{:>8} {:>8} {:>12} {:>12} {:>8} {:>12} {:>8}",
+        "enemies", "lasers", "naive", "grid", "speedup", "grid+narrow", "hits"
+    );
+    for (enemies, lasers) in [(55, 100), (55, 1000), (200, 2000), (200, 5000), (500, 10000)] {
+        let (enemies, lasers) = scene(enemies, lasers);
+
+        let (naive_time, naive_hits) = measure(|| naive(&enemies, &lasers));
+        let (grid_time, grid_hits) = measure(|| grid(&mut collision_grid, &enemies, &lasers));
+        assert_eq!(naive_hits, grid_hits, "la rejilla debe encontrar las mismas colisiones");
+
+        let (narrow_time, narrow_hits) =
+            measure(|| narrow(&mut collision_grid, &mut shapes, &enemies, &lasers));
+        assert_eq!(
+            narrow_hits,
+            narrow_all_pairs(&enemies, &lasers),
+            "la rejilla no debe perder pares de la fase estrecha"
+        );
+
+        println!(
+            "{:>8} {:>8} {:>12?} {:>12?} {:>7.1}x {:>12?} {:>8}",
+            enemies.len(),
+            lasers.len(),
+            naive_time,
+            grid_time,
+            naive_time.as_secs_f64() / grid_time.as_secs_f64(),
+            narrow_time,
+            format!("{grid_hits}/{narrow_hits}")
+        );
+    }
+}
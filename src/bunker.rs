@@ -0,0 +1,309 @@
+use crate::collision::{layer, Collider, CollisionEvent, Hitbox, WorldShape};
+use crate::components::{Enemy, Laser, SpriteSize, Velocity};
+use crate::rng::GameRng;
+use crate::state::{despawn_with, GameState};
+use crate::{Playfield, SimulationSet};
+
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use rand::Rng;
+
+const BUNKER_COUNT: usize = 4;
+// distancia del centro de los búnkeres al borde inferior de la pantalla
+const BUNKER_BOTTOM_MARGIN: f32 = 230.;
+const BUNKER_CELL_SIZE: f32 = 8.;
+const BUNKER_COLOR: Color = Color::rgb(0.3, 0.9, 0.4);
+// radio del cráter de un impacto, en celdas; el borde se destruye al azar
+const BUNKER_CRATER_RADIUS: f32 = 1.8;
+const BUNKER_CRATER_EDGE_CHANCE: f64 = 0.5;
+
+// forma de cada búnker, una fila por línea ('#' = celda sólida)
+const BUNKER_SHAPE: [&str; 12] = [
+    "    ##############    ",
+    "   ################   ",
+    "  ##################  ",
+    " #################### ",
+    "######################",
+    "######################",
+    "######################",
+    "######################",
+    "#######        #######",
+    "######          ######",
+    "#####            #####",
+    "#####            #####",
+];
+
+/// Component - Búnker defensivo: máscara de celdas que se desgasta con los impactos
+#[derive(Component)]
+pub struct Bunker {
+    cells: Vec<Option<Entity>>, // sprite de cada celda viva, fila a fila
+    rows: usize,
+    columns: usize,
+}
+
+/// Component - Sprite de una celda de un búnker
+#[derive(Component)]
+pub struct BunkerCell;
+
+impl Bunker {
+    fn size(&self) -> Vec2 {
+        Vec2::new(self.columns as f32, self.rows as f32) * BUNKER_CELL_SIZE
+    }
+
+    // posición del centro de una celda respecto al centro del búnker
+    fn cell_offset(rows: usize, columns: usize, row: usize, column: usize) -> Vec2 {
+        Vec2::new(
+            (column as f32 + 0.5 - columns as f32 / 2.) * BUNKER_CELL_SIZE,
+            (rows as f32 / 2. - row as f32 - 0.5) * BUNKER_CELL_SIZE,
+        )
+    }
+
+    /// rango de filas y columnas que se solapan con un rectángulo (relativo al centro del búnker)
+    fn cell_range(&self, min: Vec2, max: Vec2) -> Option<(usize, usize, usize, usize)> {
+        let half = self.size() / 2.;
+        if max.x <= -half.x || min.x >= half.x || max.y <= -half.y || min.y >= half.y {
+            return None;
+        }
+
+        let column_min = ((min.x + half.x) / BUNKER_CELL_SIZE).floor().max(0.) as usize;
+        let column_max = (((max.x + half.x) / BUNKER_CELL_SIZE).ceil() as usize).min(self.columns);
+        let row_min = ((half.y - max.y) / BUNKER_CELL_SIZE).floor().max(0.) as usize;
+        let row_max = (((half.y - min.y) / BUNKER_CELL_SIZE).ceil() as usize).min(self.rows);
+        Some((row_min, row_max, column_min, column_max))
+    }
+
+    /// rango de celdas bajo la caja de una forma del mundo, con el búnker en `position`
+    fn shape_range(&self, position: Vec2, shape: &WorldShape) -> Option<(usize, usize, usize, usize)> {
+        let bounds = shape.bounds();
+        self.cell_range(bounds.min - position, bounds.max - position)
+    }
+
+    /// true si la celda está viva y la forma la toca; `cell` es un buffer reutilizable
+    fn touches(
+        &self,
+        position: Vec2,
+        (row, column): (usize, usize),
+        shape: &WorldShape,
+        cell: &mut WorldShape,
+    ) -> bool {
+        if self.cells[row * self.columns + column].is_none() {
+            return false;
+        }
+
+        let center = position + Self::cell_offset(self.rows, self.columns, row, column);
+        cell.set(
+            &Transform::from_translation(center.extend(0.)),
+            &SpriteSize(Vec2::splat(BUNKER_CELL_SIZE)),
+            None,
+        );
+        shape.overlaps(cell)
+    }
+
+    /// primera celda viva que toca el laser, recorriendo las filas en su dirección
+    fn impact(
+        &self,
+        position: Vec2,
+        laser: &WorldShape,
+        cell: &mut WorldShape,
+        upward: bool,
+    ) -> Option<(usize, usize)> {
+        let (row_min, row_max, column_min, column_max) = self.shape_range(position, laser)?;
+
+        for i in row_min..row_max {
+            let row = if upward { row_max - 1 - (i - row_min) } else { i };
+            for column in column_min..column_max {
+                if self.touches(position, (row, column), laser, cell) {
+                    return Some((row, column));
+                }
+            }
+        }
+        None
+    }
+
+    fn remove_cell(&mut self, commands: &mut Commands, row: usize, column: usize) {
+        if let Some(cell) = self.cells[row * self.columns + column].take() {
+            commands.entity(cell).despawn();
+        }
+    }
+}
+
+/// Plugin - Búnkeres destructibles cerca del borde inferior
+pub struct BunkerPlugin;
+
+impl Plugin for BunkerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(GameState::Menu), bunker_spawn_system)
+            // aplicar los despawns antes de las demás colisiones, así un laser
+            // detenido por un búnker no llega a dañar a nadie en el mismo tick
+            .add_systems(
+                FixedUpdate,
+                (bunker_laser_hit_system, bunker_enemy_overlap_system, apply_deferred)
+                    .chain()
+                    .in_set(SimulationSet::Obstacles),
+            )
+            .add_systems(
+                OnEnter(GameState::Menu),
+                (despawn_with::<Bunker>, despawn_with::<BunkerCell>),
+            );
+    }
+}
+
+fn bunker_spawn_system(mut commands: Commands, playfield: Res<Playfield>) {
+    let rows = BUNKER_SHAPE.len();
+    let columns = BUNKER_SHAPE[0].len();
+    let rect = playfield.rect;
+    let y = rect.min.y + BUNKER_BOTTOM_MARGIN;
+
+    for i in 0..BUNKER_COUNT {
+        let x = rect.min.x + rect.width() * (i + 1) as f32 / (BUNKER_COUNT + 1) as f32;
+        let center = Vec2::new(x, y);
+
+        let mut cells = Vec::with_capacity(rows * columns);
+        for (row, line) in BUNKER_SHAPE.iter().enumerate() {
+            for (column, c) in line.chars().enumerate() {
+                if c != '#' {
+                    cells.push(None);
+                    continue;
+                }
+
+                let position = center + Bunker::cell_offset(rows, columns, row, column);
+                let cell = commands
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: BUNKER_COLOR,
+                            custom_size: Some(Vec2::splat(BUNKER_CELL_SIZE)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(position.extend(1.)),
+                        ..Default::default()
+                    })
+                    .insert(BunkerCell)
+                    .id();
+                cells.push(Some(cell));
+            }
+        }
+
+        let bunker = Bunker {
+            cells,
+            rows,
+            columns,
+        };
+        commands
+            .spawn(TransformBundle::from_transform(Transform::from_translation(
+                center.extend(1.),
+            )))
+            .insert(SpriteSize(bunker.size()))
+            .insert(Collider::new(
+                layer::BUNKER,
+                layer::PLAYER_LASER | layer::ENEMY_LASER | layer::ENEMY,
+            ))
+            .insert(bunker);
+    }
+}
+
+// los lasers de ambos bandos se detienen en la primera celda viva y abren un cráter
+// lo que se necesita de un laser para saber qué celdas toca y en qué sentido
+type LaserImpact = (
+    Entity,
+    &'static Transform,
+    &'static SpriteSize,
+    Option<&'static Hitbox>,
+    &'static Velocity,
+);
+
+fn bunker_laser_hit_system(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    mut events: EventReader<CollisionEvent>,
+    mut bunker_query: Query<(&Transform, &mut Bunker)>,
+    laser_query: Query<LaserImpact, With<Laser>>,
+    mut shapes: Local<(WorldShape, WorldShape)>,
+) {
+    let (laser_shape, cell_shape) = &mut *shapes;
+
+    for event in events.iter() {
+        if !event.is(layer::BUNKER, layer::PLAYER_LASER | layer::ENEMY_LASER) {
+            continue;
+        }
+
+        let (
+            Ok((bunker_tf, mut bunker)),
+            Ok((laser_entity, laser_tf, laser_size, laser_hitbox, velocity)),
+        ) = (bunker_query.get_mut(event.entity), laser_query.get(event.other))
+        else {
+            continue;
+        };
+
+        // las celdas se comparan con la hitbox del laser, girada como el sprite
+        laser_shape.set(laser_tf, laser_size, laser_hitbox);
+        let upward = velocity.y > 0.;
+        let Some((impact_row, impact_column)) =
+            bunker.impact(bunker_tf.translation.xy(), laser_shape, cell_shape, upward)
+        else {
+            continue;
+        };
+
+        commands.entity(laser_entity).despawn();
+
+        // abrir el cráter alrededor de la celda de impacto
+        let radius = BUNKER_CRATER_RADIUS.ceil() as isize;
+        for dr in -radius..=radius {
+            for dc in -radius..=radius {
+                let (row, column) = (impact_row as isize + dr, impact_column as isize + dc);
+                if row < 0
+                    || column < 0
+                    || row >= bunker.rows as isize
+                    || column >= bunker.columns as isize
+                {
+                    continue;
+                }
+
+                let distance = ((dr * dr + dc * dc) as f32).sqrt();
+                if distance > BUNKER_CRATER_RADIUS
+                    || (distance > 1. && !rng.gen_bool(BUNKER_CRATER_EDGE_CHANCE))
+                {
+                    continue;
+                }
+
+                bunker.remove_cell(&mut commands, row as usize, column as usize);
+            }
+        }
+    }
+}
+
+// los enemigos que bajan hasta los búnkeres destruyen las celdas que tocan
+fn bunker_enemy_overlap_system(
+    mut commands: Commands,
+    mut events: EventReader<CollisionEvent>,
+    mut bunker_query: Query<(&Transform, &mut Bunker)>,
+    enemy_query: Query<(&Transform, &SpriteSize, Option<&Hitbox>), With<Enemy>>,
+    mut shapes: Local<(WorldShape, WorldShape)>,
+) {
+    let (enemy_shape, cell_shape) = &mut *shapes;
+
+    for event in events.iter() {
+        if !event.is(layer::BUNKER, layer::ENEMY) {
+            continue;
+        }
+
+        let (Ok((bunker_tf, mut bunker)), Ok((enemy_tf, enemy_size, enemy_hitbox))) =
+            (bunker_query.get_mut(event.entity), enemy_query.get(event.other))
+        else {
+            continue;
+        };
+
+        enemy_shape.set(enemy_tf, enemy_size, enemy_hitbox);
+        let position = bunker_tf.translation.xy();
+        if let Some((row_min, row_max, column_min, column_max)) =
+            bunker.shape_range(position, enemy_shape)
+        {
+            for row in row_min..row_max {
+                for column in column_min..column_max {
+                    if bunker.touches(position, (row, column), enemy_shape, cell_shape) {
+                        bunker.remove_cell(&mut commands, row, column);
+                    }
+                }
+            }
+        }
+    }
+}
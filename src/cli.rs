@@ -0,0 +1,66 @@
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+
+const DEFAULT_HEADLESS_TICKS: u64 = 60 * 60; // un minuto de juego
+
+/// Opciones de línea de comandos del juego
+pub struct CliArgs {
+    pub seed: Option<u64>,       // semilla fija para el generador aleatorio
+    pub record: Option<PathBuf>, // fichero donde grabar la partida
+    pub replay: Option<PathBuf>, // fichero de replay a reproducir
+    pub headless: bool,          // simular sin ventana, render ni audio
+    pub ticks: u64,              // ticks a simular en modo headless
+    pub lives: Option<u32>,      // vidas al empezar la partida
+}
+
+impl Default for CliArgs {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            record: None,
+            replay: None,
+            headless: false,
+            ticks: DEFAULT_HEADLESS_TICKS,
+            lives: None,
+        }
+    }
+}
+
+impl CliArgs {
+    /// leer la línea de comandos; un valor que falta o no se entiende termina el programa
+    pub fn parse() -> Self {
+        Self::parse_from(env::args().skip(1)).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            process::exit(2);
+        })
+    }
+
+    pub fn parse_from(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut cli = CliArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => cli.seed = Some(value(&mut args, &arg)?),
+                "--record" => cli.record = Some(value(&mut args, &arg)?),
+                "--replay" => cli.replay = Some(value(&mut args, &arg)?),
+                "--headless" => cli.headless = true,
+                "--ticks" => cli.ticks = value(&mut args, &arg)?,
+                "--lives" => cli.lives = Some(value(&mut args, &arg)?),
+                _ => eprintln!("argumento desconocido: {arg}"),
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+// valor de una opción: sin él, o mal escrito, la partida no sería la que se pidió
+fn value<T: FromStr>(args: &mut impl Iterator<Item = String>, option: &str) -> Result<T, String> {
+    let value = args.next().ok_or_else(|| format!("falta el valor de {option}"))?;
+    value
+        .parse()
+        .map_err(|_| format!("valor no válido para {option}: {value}"))
+}
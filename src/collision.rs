@@ -0,0 +1,558 @@
+use crate::components::SpriteSize;
+
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+/// Capas de colisión, una por bit
+pub mod layer {
+    pub const PLAYER: u32 = 1 << 0;
+    pub const PLAYER_LASER: u32 = 1 << 1;
+    pub const ENEMY: u32 = 1 << 2;
+    pub const ENEMY_LASER: u32 = 1 << 3;
+    pub const POWER_UP: u32 = 1 << 4;
+    pub const BUNKER: u32 = 1 << 5;
+    pub const UFO: u32 = 1 << 6;
+}
+
+/// Component - Entidad que participa en las colisiones
+///
+/// `layer` son las capas a las que pertenece y `mask` las capas con las que quiere
+/// chocar; solo las entidades con máscara reciben eventos.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Collider {
+    pub layer: u32,
+    pub mask: u32,
+}
+
+impl Collider {
+    pub fn new(layer: u32, mask: u32) -> Self {
+        Self { layer, mask }
+    }
+}
+
+/// Forma de una hitbox, en píxeles del sprite y relativa a su centro
+#[derive(Deserialize, Clone, Debug)]
+pub enum Shape {
+    Circle { center: (f32, f32), radius: f32 },
+    /// segmento de `a` a `b` engordado `radius`
+    Capsule { a: (f32, f32), b: (f32, f32), radius: f32 },
+    /// polígono convexo, en cualquier sentido de giro
+    Polygon(Vec<(f32, f32)>),
+}
+
+impl Shape {
+    /// radios positivos y polígonos convexos de al menos tres vértices
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Shape::Circle { radius, .. } | Shape::Capsule { radius, .. } => *radius > 0.,
+            Shape::Polygon(points) => {
+                let n = points.len();
+                let mut turns = (0..n).map(|i| {
+                    let (a, b, c) = (points[i], points[(i + 1) % n], points[(i + 2) % n]);
+                    let (ab, bc) = (Vec2::new(b.0 - a.0, b.1 - a.1), Vec2::new(c.0 - b.0, c.1 - b.1));
+                    ab.perp_dot(bc)
+                });
+                n >= 3 && (turns.clone().all(|turn| turn > 0.) || turns.all(|turn| turn < 0.))
+            }
+        }
+    }
+}
+
+/// Component - Hitbox precisa, independiente de `SpriteSize`
+///
+/// Se mueve, gira y escala con la entidad; sin hitbox se usa el rectángulo de `SpriteSize`.
+#[derive(Component, Clone, Debug)]
+pub struct Hitbox(pub Shape);
+
+/// Event - Dos colliders solapados en este tick
+///
+/// `entity` es el collider cuya máscara incluye la capa de `other`; si las dos máscaras
+/// se incluyen mutuamente se envía un evento en cada sentido.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CollisionEvent {
+    pub entity: Entity,
+    pub layer: u32,
+    pub other: Entity,
+    pub other_layer: u32,
+}
+
+impl CollisionEvent {
+    /// true si `entity` pertenece a `layer` y `other` a `other_layer`
+    pub fn is(&self, layer: u32, other_layer: u32) -> bool {
+        self.layer & layer != 0 && self.other_layer & other_layer != 0
+    }
+}
+
+/// Resource - Rejilla uniforme para la fase amplia de las colisiones
+///
+/// Se rellena una vez por tick con todos los colliders; `collision_system` pide a la
+/// rejilla los colliders cercanos en lugar de comparar todos los pares.
+#[derive(Resource)]
+pub struct CollisionGrid {
+    bounds: Rect,
+    cell_size: f32,
+    columns: usize,
+    rows: usize,
+    cells: Vec<Vec<u32>>,    // índices de `entries` que tocan cada celda
+    entries: Vec<GridEntry>, // se reutilizan entre ticks para no reservar memoria
+}
+
+struct GridEntry {
+    entity: Entity,
+    layer: u32,
+    rect: Rect,
+    first_cell: (usize, usize), // primera columna y fila que ocupa
+}
+
+impl CollisionGrid {
+    /// las entidades fuera de `bounds` se guardan en las celdas del borde
+    pub fn new(bounds: Rect, cell_size: f32) -> Self {
+        let columns = (bounds.width() / cell_size).ceil().max(1.) as usize;
+        let rows = (bounds.height() / cell_size).ceil().max(1.) as usize;
+
+        Self {
+            bounds,
+            cell_size,
+            columns,
+            rows,
+            cells: vec![Vec::new(); columns * rows],
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for cell in self.cells.iter_mut() {
+            cell.clear();
+        }
+        self.entries.clear();
+    }
+
+    pub fn insert(&mut self, entity: Entity, layer: u32, rect: Rect) {
+        let (column_min, column_max, row_min, row_max) = self.cell_range(rect);
+
+        let index = self.entries.len() as u32;
+        self.entries.push(GridEntry {
+            entity,
+            layer,
+            rect,
+            first_cell: (column_min, row_min),
+        });
+
+        for row in row_min..=row_max {
+            for column in column_min..=column_max {
+                self.cells[row * self.columns + column].push(index);
+            }
+        }
+    }
+
+    /// entidades de las capas de `mask` cuyo rectángulo se solapa con el dado, cada una
+    /// una sola vez y junto a sus capas
+    pub fn query(&self, rect: Rect, mask: u32) -> impl Iterator<Item = (Entity, u32)> + '_ {
+        let (column_min, column_max, row_min, row_max) = self.cell_range(rect);
+
+        (row_min..=row_max)
+            .flat_map(move |row| (column_min..=column_max).map(move |column| (row, column)))
+            .flat_map(move |(row, column)| {
+                self.cells[row * self.columns + column]
+                    .iter()
+                    .map(move |&index| (row, column, index))
+            })
+            .filter_map(move |(row, column, index)| {
+                let entry = &self.entries[index as usize];
+                if entry.layer & mask == 0 {
+                    return None;
+                }
+
+                // una entidad en varias celdas solo se devuelve desde la primera que
+                // comparte con la consulta
+                let (entry_column, entry_row) = entry.first_cell;
+                if column != entry_column.max(column_min) || row != entry_row.max(row_min) {
+                    return None;
+                }
+
+                let overlaps = entry.rect.min.x < rect.max.x
+                    && entry.rect.max.x > rect.min.x
+                    && entry.rect.min.y < rect.max.y
+                    && entry.rect.max.y > rect.min.y;
+                overlaps.then_some((entry.entity, entry.layer))
+            })
+    }
+
+    // rango de columnas y filas (inclusivo) que cubre un rectángulo
+    fn cell_range(&self, rect: Rect) -> (usize, usize, usize, usize) {
+        let min = ((rect.min - self.bounds.min) / self.cell_size).floor();
+        let max = ((rect.max - self.bounds.min) / self.cell_size).floor();
+        let clamp = |value: f32, len: usize| (value.max(0.) as usize).min(len - 1);
+
+        (
+            clamp(min.x, self.columns),
+            clamp(max.x, self.columns),
+            clamp(min.y, self.rows),
+            clamp(max.y, self.rows),
+        )
+    }
+}
+
+/// Forma en coordenadas del mundo: núcleo convexo (punto, segmento o polígono) engordado
+/// `radius`; es lo que compara la fase estrecha
+///
+/// Se rellena con `set` sobre la misma instancia para no reservar memoria en cada par.
+#[derive(Default)]
+pub struct WorldShape {
+    points: Vec<Vec2>,
+    radius: f32,
+}
+
+impl WorldShape {
+    pub fn set(&mut self, transform: &Transform, size: &SpriteSize, hitbox: Option<&Hitbox>) {
+        self.points.clear();
+
+        let Some(hitbox) = hitbox else {
+            // sin hitbox, el rectángulo del sprite sin girar
+            let rect = Rect::from_center_size(transform.translation.xy(), size.0 * transform.scale.xy());
+            self.points.extend([
+                rect.min,
+                Vec2::new(rect.max.x, rect.min.y),
+                rect.max,
+                Vec2::new(rect.min.x, rect.max.y),
+            ]);
+            self.radius = 0.;
+            return;
+        };
+
+        let scale = transform.scale.xy();
+        let to_world = |(x, y): (f32, f32)| {
+            let local = Vec2::new(x, y) * scale;
+            transform.translation.xy() + (transform.rotation * local.extend(0.)).xy()
+        };
+        // con escalas distintas en cada eje el radio sigue al eje menor
+        let radius_scale = scale.x.abs().min(scale.y.abs());
+
+        match &hitbox.0 {
+            Shape::Circle { center, radius } => {
+                self.points.push(to_world(*center));
+                self.radius = radius * radius_scale;
+            }
+            Shape::Capsule { a, b, radius } => {
+                self.points.extend([to_world(*a), to_world(*b)]);
+                self.radius = radius * radius_scale;
+            }
+            Shape::Polygon(points) => {
+                self.points.extend(points.iter().copied().map(to_world));
+                self.radius = 0.;
+            }
+        }
+    }
+
+    pub fn bounds(&self) -> Rect {
+        let (min, max) = self.points.iter().fold(
+            (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY)),
+            |(min, max), &point| (min.min(point), max.max(point)),
+        );
+        Rect {
+            min: min - self.radius,
+            max: max + self.radius,
+        }
+    }
+
+    // un punto es una arista degenerada y un segmento no se cierra sobre sí mismo
+    fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        let n = self.points.len();
+        let count = if n <= 2 { 1 } else { n };
+        (0..count).map(move |i| (self.points[i], self.points[(i + 1) % n]))
+    }
+
+    // ejes de separación: normal y dirección de cada arista; la dirección solo hace
+    // falta en los segmentos, en los polígonos no cambia el resultado
+    fn axes(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.edges()
+            .flat_map(|(a, b)| [(b - a).perp(), b - a])
+            .filter(|axis| *axis != Vec2::ZERO)
+    }
+
+    fn project(&self, axis: Vec2) -> (f32, f32) {
+        self.points.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+            let projection = point.dot(axis);
+            (min.min(projection), max.max(projection))
+        })
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        if self.cores_intersect(other) {
+            return true;
+        }
+
+        let radius = self.radius + other.radius;
+        radius > 0. && self.distance_squared(other) < radius * radius
+    }
+
+    // teorema del eje separador; dos puntos no tienen ejes y se resuelven con la distancia
+    fn cores_intersect(&self, other: &Self) -> bool {
+        let mut axes = self.axes().chain(other.axes()).peekable();
+        if axes.peek().is_none() {
+            return false;
+        }
+
+        axes.all(|axis| {
+            let (a_min, a_max) = self.project(axis);
+            let (b_min, b_max) = other.project(axis);
+            a_min < b_max && b_min < a_max
+        })
+    }
+
+    // distancia entre núcleos que no se cortan: siempre se alcanza desde un vértice
+    // de uno de ellos a una arista del otro
+    fn distance_squared(&self, other: &Self) -> f32 {
+        let one_way = |from: &Self, to: &Self| {
+            from.points
+                .iter()
+                .flat_map(|&point| to.edges().map(move |edge| segment_distance_squared(point, edge)))
+                .fold(f32::INFINITY, f32::min)
+        };
+        one_way(self, other).min(one_way(other, self))
+    }
+}
+
+fn segment_distance_squared(point: Vec2, (a, b): (Vec2, Vec2)) -> f32 {
+    let ab = b - a;
+    let t = if ab == Vec2::ZERO {
+        0.
+    } else {
+        ((point - a).dot(ab) / ab.length_squared()).clamp(0., 1.)
+    };
+    (a + ab * t).distance_squared(point)
+}
+
+// repartir en la rejilla todos los colliders
+pub fn collision_grid_system(
+    mut grid: ResMut<CollisionGrid>,
+    query: Query<(Entity, &Transform, &SpriteSize, &Collider, Option<&Hitbox>)>,
+    mut shape: Local<WorldShape>,
+) {
+    grid.clear();
+    for (entity, transform, size, collider, hitbox) in query.iter() {
+        shape.set(transform, size, hitbox);
+        grid.insert(entity, collider.layer, shape.bounds());
+    }
+}
+
+// enviar un evento por cada collider solapado con otro de las capas de su máscara
+pub fn collision_system(
+    grid: Res<CollisionGrid>,
+    query: Query<(Entity, &Transform, &SpriteSize, &Collider, Option<&Hitbox>)>,
+    mut events: EventWriter<CollisionEvent>,
+    mut shapes: Local<(WorldShape, WorldShape)>,
+) {
+    let (shape, other_shape) = &mut *shapes;
+
+    for (entity, transform, size, collider, hitbox) in query.iter() {
+        if collider.mask == 0 {
+            continue;
+        }
+
+        shape.set(transform, size, hitbox);
+        for (other, other_layer) in grid.query(shape.bounds(), collider.mask) {
+            if other == entity {
+                continue;
+            }
+
+            // la rejilla ya compara rectángulos; la fase estrecha solo hace falta si
+            // alguno de los dos tiene hitbox
+            let Ok((_, other_tf, other_size, _, other_hitbox)) = query.get(other) else {
+                continue;
+            };
+            if hitbox.is_some() || other_hitbox.is_some() {
+                other_shape.set(other_tf, other_size, other_hitbox);
+                if !shape.overlaps(other_shape) {
+                    continue;
+                }
+            }
+
+            events.send(CollisionEvent {
+                entity,
+                layer: collider.layer,
+                other,
+                other_layer,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    const UNIT: SpriteSize = SpriteSize(Vec2::ONE);
+
+    fn square(half: f32) -> Shape {
+        Shape::Polygon(vec![(-half, -half), (half, -half), (half, half), (-half, half)])
+    }
+
+    fn circle(radius: f32) -> Shape {
+        Shape::Circle {
+            center: (0., 0.),
+            radius,
+        }
+    }
+
+    // cápsula vertical de 20 de largo (sin contar los extremos)
+    fn capsule(radius: f32) -> Shape {
+        Shape::Capsule {
+            a: (0., -10.),
+            b: (0., 10.),
+            radius,
+        }
+    }
+
+    fn world(shape: Shape, position: (f32, f32), angle: f32, scale: (f32, f32)) -> WorldShape {
+        let transform = Transform {
+            translation: Vec3::new(position.0, position.1, 0.),
+            rotation: Quat::from_rotation_z(angle),
+            scale: Vec3::new(scale.0, scale.1, 1.),
+        };
+        let mut world = WorldShape::default();
+        world.set(&transform, &UNIT, Some(&Hitbox(shape)));
+        world
+    }
+
+    fn at(shape: Shape, x: f32, y: f32) -> WorldShape {
+        world(shape, (x, y), 0., (1., 1.))
+    }
+
+    // comprobar en los dos sentidos, el resultado no debe depender del orden
+    fn overlaps(a: &WorldShape, b: &WorldShape) -> bool {
+        let result = a.overlaps(b);
+        assert_eq!(result, b.overlaps(a), "resultado asimétrico");
+        result
+    }
+
+    #[test]
+    fn circle_circle() {
+        assert!(overlaps(&at(circle(1.), 0., 0.), &at(circle(1.), 1.9, 0.)));
+        assert!(!overlaps(&at(circle(1.), 0., 0.), &at(circle(1.), 2.1, 0.)));
+        assert!(overlaps(&at(circle(1.), 0., 0.), &at(circle(1.), 0., 0.)));
+    }
+
+    #[test]
+    fn capsule_polygon() {
+        let square = at(square(5.), 0., 0.);
+
+        // el extremo redondeado entra en el cuadrado por arriba
+        assert!(overlaps(&square, &at(capsule(1.), 0., 15.5)));
+        assert!(!overlaps(&square, &at(capsule(1.), 0., 16.5)));
+
+        // a un lado, solo cuenta el radio
+        assert!(overlaps(&square, &at(capsule(1.), 5.5, 0.)));
+        assert!(!overlaps(&square, &at(capsule(1.), 6.5, 0.)));
+
+        // cerca de la esquina la distancia es diagonal, no la de la caja
+        let corner = at(circle(1.), 5.8, 5.8);
+        assert!(!overlaps(&square, &corner));
+    }
+
+    #[test]
+    fn polygon_inside_polygon() {
+        let outer = at(square(10.), 0., 0.);
+        let inner = at(square(1.), 3., -2.);
+        assert!(overlaps(&outer, &inner));
+
+        // también un círculo y una cápsula enteros dentro de un polígono
+        assert!(overlaps(&outer, &at(circle(1.), 0., 0.)));
+        assert!(overlaps(&at(square(20.), 0., 0.), &at(capsule(1.), 0., 0.)));
+    }
+
+    #[test]
+    fn touching_is_not_overlapping() {
+        // aristas en contacto exacto, como el test de rectángulos de la rejilla
+        assert!(!overlaps(&at(square(1.), 0., 0.), &at(square(1.), 2., 0.)));
+        assert!(!overlaps(&at(square(1.), 0., 0.), &at(square(1.), 2., 2.)));
+        assert!(overlaps(&at(square(1.), 0., 0.), &at(square(1.), 1.99, 0.)));
+
+        // círculos tangentes
+        assert!(!overlaps(&at(circle(1.), 0., 0.), &at(circle(1.), 2., 0.)));
+        // círculo tangente a una arista
+        assert!(!overlaps(&at(square(1.), 0., 0.), &at(circle(1.), 2., 0.)));
+    }
+
+    #[test]
+    fn rotated_shapes() {
+        // cápsula vertical girada 90º: queda horizontal
+        let lying = world(capsule(1.), (0., 0.), FRAC_PI_2, (1., 1.));
+        assert!(overlaps(&lying, &at(circle(1.), 10.5, 0.)));
+        assert!(!overlaps(&lying, &at(circle(1.), 0., 2.5)));
+
+        // cuadrado girado 45º: la esquina llega a sqrt(2), el lado no
+        let diamond = world(square(1.), (0., 0.), FRAC_PI_2 / 2., (1., 1.));
+        assert!(overlaps(&diamond, &at(circle(0.1), 1.35, 0.)));
+        assert!(!overlaps(&diamond, &at(circle(0.1), 1.05, 1.05)));
+
+        // cápsulas cruzadas en X
+        let diagonal = world(capsule(0.5), (0., 0.), FRAC_PI_2 / 2., (1., 1.));
+        let other = world(capsule(0.5), (0., 0.), -FRAC_PI_2 / 2., (1., 1.));
+        assert!(overlaps(&diagonal, &other));
+    }
+
+    #[test]
+    fn non_uniform_scale() {
+        // cuadrado estirado al doble en x
+        let wide = world(square(1.), (0., 0.), 0., (2., 1.));
+        assert!(overlaps(&wide, &at(circle(0.1), 1.95, 0.)));
+        assert!(!overlaps(&wide, &at(circle(0.1), 0., 1.2)));
+
+        // cápsula alargada en y: el segmento se estira y el radio sigue al eje menor
+        let beam = world(capsule(1.), (0., 0.), 0., (0.5, 3.));
+        assert_eq!(beam.bounds(), Rect::new(-0.5, -30.5, 0.5, 30.5));
+        assert!(overlaps(&beam, &at(circle(0.1), 0., 30.5)));
+        assert!(!overlaps(&beam, &at(circle(0.1), 0.7, 0.)));
+    }
+
+    #[test]
+    fn sprite_rect_without_hitbox() {
+        let transform = Transform::from_scale(Vec3::new(2., 1., 1.));
+        let mut rect = WorldShape::default();
+        rect.set(&transform, &SpriteSize(Vec2::new(4., 4.)), None);
+
+        assert_eq!(rect.bounds(), Rect::new(-4., -2., 4., 2.));
+        assert!(overlaps(&rect, &at(circle(1.), 4.5, 0.)));
+        assert!(!overlaps(&rect, &at(circle(1.), 0., 3.5)));
+    }
+
+    #[test]
+    fn reused_shape_forgets_previous_points() {
+        let mut shape = at(square(10.), 0., 0.);
+        shape.set(&Transform::default(), &UNIT, Some(&Hitbox(circle(1.))));
+        assert!(!overlaps(&shape, &at(circle(1.), 8., 8.)));
+    }
+
+    #[test]
+    fn segment_distance() {
+        let segment = (Vec2::new(0., 0.), Vec2::new(10., 0.));
+        assert_eq!(segment_distance_squared(Vec2::new(5., 3.), segment), 9.);
+        assert_eq!(segment_distance_squared(Vec2::new(-3., 4.), segment), 25.);
+        assert_eq!(segment_distance_squared(Vec2::new(13., 0.), segment), 9.);
+
+        // segmento degenerado en un punto
+        let point = (Vec2::new(1., 1.), Vec2::new(1., 1.));
+        assert_eq!(segment_distance_squared(Vec2::new(4., 5.), point), 25.);
+    }
+
+    #[test]
+    fn shape_validation() {
+        assert!(square(1.).is_valid());
+        assert!(circle(1.).is_valid());
+        assert!(capsule(1.).is_valid());
+
+        // cualquier sentido de giro
+        assert!(Shape::Polygon(vec![(0., 0.), (0., 1.), (1., 0.)]).is_valid());
+
+        assert!(!circle(0.).is_valid());
+        assert!(!capsule(-1.).is_valid());
+        assert!(!Shape::Polygon(vec![(0., 0.), (1., 0.)]).is_valid());
+        // punta hacia dentro
+        assert!(!Shape::Polygon(vec![(0., 0.), (2., 0.), (1., 0.5), (2., 2.), (0., 2.)]).is_valid());
+        // vértices alineados
+        assert!(!Shape::Polygon(vec![(0., 0.), (1., 0.), (2., 0.), (1., 1.)]).is_valid());
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::time::{Timer, TimerMode};
 use bevy::math::{Vec2, Vec3};
-use bevy::prelude::Component;
+use bevy::prelude::{Color, Component, Entity};
+use crate::level::MovementStyle;
 
 // region:      --- Common Components ---
 #[derive(Component)]
@@ -9,9 +10,19 @@ pub struct Velocity {
     pub y: f32,
 }
 
+/// Qué hacer con una entidad móvil al llegar a los bordes del área de juego
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    #[default]
+    Despawn, // despawnear al alejarse un margen fuera del área
+    Clamp,   // no salir del área
+    Wrap,    // reaparecer por el borde opuesto
+    Free,    // sin límites
+}
+
 #[derive(Component)]
 pub struct Movable {
-    pub auto_despawn: bool,
+    pub boundary: Boundary,
 }
 
 #[derive(Component)]
@@ -20,11 +31,57 @@ pub struct Laser;
 #[derive(Component)]
 pub struct SpriteSize(pub Vec2);
 
+/// Component - Puntos de vida de enemigos y del jugador
+#[derive(Component)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+impl Health {
+    pub fn new(max: u32) -> Self {
+        Self { current: max, max }
+    }
+
+    /// restar `amount` puntos de vida, devuelve true si llega a cero
+    pub fn damage(&mut self, amount: u32) -> bool {
+        self.current = self.current.saturating_sub(amount);
+        self.current == 0
+    }
+}
+
+/// Component - Daño que causa un laser al impactar
+#[derive(Component)]
+pub struct Damage(pub u32);
+
+/// Component - Destello al recibir daño sin ser destruido (activo mientras `time_left > 0`)
+#[derive(Component, Default)]
+pub struct DamageFlash {
+    pub time_left: f32,
+    pub color: Option<Color>, // color original del sprite, guardado al empezar
+}
+
 impl From<(f32, f32)> for SpriteSize {
     fn from(val: (f32, f32)) -> Self {
         SpriteSize(Vec2::new(val.0, val.1))
     }
 }
+
+/// Component - Posiciones de los dos últimos ticks de simulación, para interpolar al renderizar
+#[derive(Component)]
+pub struct Interpolated {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl From<Vec3> for Interpolated {
+    fn from(translation: Vec3) -> Self {
+        Interpolated {
+            previous: translation,
+            current: translation,
+        }
+    }
+}
 // endregion:   --- Common Components ---
 
 // region:      --- Player Components ---
@@ -33,6 +90,12 @@ pub struct Player;
 
 #[derive(Component)]
 pub struct FromPlayer;
+
+/// Component - Laser que atraviesa enemigos, dañando a cada uno una sola vez
+#[derive(Component, Default)]
+pub struct Piercing {
+    pub hits: Vec<Entity>,
+}
 // endregion:   --- Player Components ---
 
 // region:      --- Enemy Components ---
@@ -40,6 +103,13 @@ pub struct FromPlayer;
 #[derive(Component)]
 pub struct Enemy;
 
+/// Component - Estadísticas del tipo de enemigo
+#[derive(Component)]
+pub struct EnemyStats {
+    pub score: u32,
+    pub movement: MovementStyle,
+}
+
 #[derive(Component)]
 pub struct FromEnemy;
 
@@ -69,3 +139,22 @@ pub struct PlayerInvincible {
 }
 
 // endregion:   --- Explosion Components ---
+
+// region:      --- HUD Components ---
+
+#[derive(Component)]
+pub struct ScoreText;
+
+#[derive(Component)]
+pub struct LivesText;
+
+#[derive(Component)]
+pub struct BossHealthBar;
+
+#[derive(Component)]
+pub struct BossHealthFill;
+
+#[derive(Component)]
+pub struct Background;
+
+// endregion:   --- HUD Components ---
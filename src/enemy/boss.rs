@@ -0,0 +1,225 @@
+use super::pattern::BulletEmitter;
+use super::spawn_enemy;
+use super::wave::WaveState;
+use crate::components::{ExplosionToSpawn, Health};
+use crate::level::{BossDef, BossMovement, CurrentLevel};
+use crate::rng::GameRng;
+use crate::{EnemyCount, GameTextures, Playfield, Scoreboard, TIME_STEP};
+
+use bevy::prelude::*;
+
+// velocidad de entrada y de movimiento en las fases Hover
+const BOSS_SPEED: f32 = 300.;
+// distancia del centro del jefe al borde superior de la pantalla
+const BOSS_TOP_MARGIN: f32 = 250.;
+// distancia a los bordes laterales en las fases Sweep
+const BOSS_SWEEP_MARGIN: f32 = 250.;
+
+/// Component - Jefe: estado compartido por todas sus partes
+#[derive(Component)]
+pub struct Boss {
+    pub def: BossDef,
+    pub core: Entity,
+    pub position: Vec2,
+    pub anchor: Vec2, // centro de las fases Hover
+    pub phase: usize,
+    pub health: u32, // vida restante sumando todas las partes
+    pub max_health: u32,
+    time: f32,
+    sweep_dir: f32,
+}
+
+impl Boss {
+    pub fn health_fraction(&self) -> f32 {
+        if self.max_health == 0 {
+            return 0.;
+        }
+        self.health as f32 / self.max_health as f32
+    }
+}
+
+/// Component - Parte de un jefe con su propia hitbox (torretas, núcleo)
+#[derive(Component)]
+pub struct BossPart {
+    pub boss: Entity,
+    pub offset: Vec2, // posición respecto al centro del jefe
+}
+
+// crear el jefe de la oleada cuando se han eliminado todos sus enemigos
+pub fn boss_spawn_system(
+    mut commands: Commands,
+    game_textures: Res<GameTextures>,
+    current_level: Res<CurrentLevel>,
+    mut wave_state: ResMut<WaveState>,
+    mut enemy_count: ResMut<EnemyCount>,
+    playfield: Res<Playfield>,
+) {
+    let level = &current_level.0;
+    let Some(wave) = wave_state.current(level) else {
+        return;
+    };
+    let Some(def) = wave.boss.as_ref().and_then(|name| level.bosses.get(name)) else {
+        return;
+    };
+
+    if wave_state.boss_spawned || wave_state.spawned < wave.total() || enemy_count.0 > 0 {
+        return;
+    }
+
+    // el jefe entra desde arriba hasta su posición
+    let anchor = Vec2::new(0., playfield.rect.max.y - BOSS_TOP_MARGIN);
+    let position = Vec2::new(0., playfield.rect.max.y + BOSS_TOP_MARGIN);
+
+    let boss = commands.spawn_empty().id();
+    let mut core = boss;
+    let mut max_health = 0;
+
+    for part in def.parts.iter() {
+        let archetype = level.archetype(Some(&part.archetype));
+        let offset = Vec2::from(part.offset);
+        let translation = (position + offset).extend(10.);
+
+        let entity = spawn_enemy(&mut commands, &game_textures, &archetype, translation)
+            .insert(BossPart { boss, offset })
+            .id();
+        if part.core {
+            core = entity;
+        }
+        max_health += archetype.hp;
+    }
+
+    commands.entity(boss).insert(Boss {
+        def: def.clone(),
+        core,
+        position,
+        anchor,
+        phase: 0,
+        health: max_health,
+        max_health,
+        time: 0.,
+        sweep_dir: 1.,
+    });
+
+    enemy_count.0 += def.parts.len() as u32;
+    wave_state.boss_spawned = true;
+}
+
+// actualizar la vida total y la fase según la vida restante
+pub fn boss_phase_system(
+    mut boss_query: Query<(Entity, &mut Boss)>,
+    part_query: Query<(&BossPart, &Health)>,
+) {
+    for (entity, mut boss) in boss_query.iter_mut() {
+        boss.health = part_query
+            .iter()
+            .filter(|(part, _)| part.boss == entity)
+            .map(|(_, health)| health.current)
+            .sum();
+
+        // las fases están ordenadas de más a menos vida
+        let fraction = boss.health_fraction();
+        let phase = boss
+            .def
+            .phases
+            .iter()
+            .rposition(|phase| fraction <= phase.health)
+            .unwrap_or(0);
+        if phase != boss.phase {
+            boss.phase = phase;
+        }
+    }
+}
+
+pub fn boss_fire_system(
+    mut game_rng: ResMut<GameRng>,
+    wave_state: Res<WaveState>,
+    boss_query: Query<(Entity, &Boss)>,
+    mut part_query: Query<(&BossPart, &mut BulletEmitter)>,
+) {
+    for (entity, boss) in boss_query.iter() {
+        let phase = &boss.def.phases[boss.phase];
+        let delta = TIME_STEP * phase.fire_rate * wave_state.difficulty();
+
+        // el arma de la fase sustituye a la de cada parte
+        for (part, mut emitter) in part_query.iter_mut() {
+            if part.boss != entity {
+                continue;
+            }
+
+            let Some(weapon) = phase.weapon.or(emitter.weapon) else {
+                continue;
+            };
+            if emitter.tick_cooldown(delta, &weapon, &mut *game_rng) {
+                emitter.trigger(phase.weapon);
+            }
+        }
+    }
+}
+
+pub fn boss_movement_system(
+    playfield: Res<Playfield>,
+    mut boss_query: Query<(Entity, &mut Boss)>,
+    mut part_query: Query<(&mut Transform, &BossPart)>,
+) {
+    for (entity, mut boss) in boss_query.iter_mut() {
+        boss.time += TIME_STEP;
+
+        let (target, speed) = match boss.def.phases[boss.phase].movement {
+            BossMovement::Hover {
+                amplitude,
+                frequency,
+            } => {
+                let t = boss.time * frequency;
+                let offset = Vec2::new(amplitude.0 * t.sin(), amplitude.1 * (2. * t).sin());
+                (boss.anchor + offset, BOSS_SPEED)
+            }
+            BossMovement::Sweep { speed } => {
+                // cambiar de dirección al llegar a un borde
+                let limit = playfield.rect.max.x - BOSS_SWEEP_MARGIN;
+                if (boss.position.x - boss.sweep_dir * limit).abs() < 1. {
+                    boss.sweep_dir = -boss.sweep_dir;
+                }
+                (Vec2::new(boss.sweep_dir * limit, boss.anchor.y), speed)
+            }
+        };
+
+        // avanzar hacia el objetivo sin superar la velocidad máxima
+        let delta = target - boss.position;
+        let max_distance = speed * TIME_STEP;
+        boss.position = if delta.length() <= max_distance {
+            target
+        } else {
+            boss.position + delta.normalize() * max_distance
+        };
+
+        for (mut transform, part) in part_query.iter_mut().filter(|(_, part)| part.boss == entity) {
+            let position = boss.position + part.offset;
+            let translation = &mut transform.translation;
+            (translation.x, translation.y) = (position.x, position.y);
+        }
+    }
+}
+
+// al destruir el núcleo explotan todas las partes restantes
+pub fn boss_death_system(
+    mut commands: Commands,
+    mut enemy_count: ResMut<EnemyCount>,
+    mut scoreboard: ResMut<Scoreboard>,
+    boss_query: Query<(Entity, &Boss)>,
+    part_query: Query<(Entity, &Transform, &BossPart)>,
+) {
+    for (entity, boss) in boss_query.iter() {
+        if part_query.contains(boss.core) {
+            continue;
+        }
+
+        for (part_entity, tf, _) in part_query.iter().filter(|(_, _, part)| part.boss == entity) {
+            commands.entity(part_entity).despawn();
+            commands.spawn(ExplosionToSpawn(tf.translation));
+            enemy_count.0 -= 1;
+        }
+
+        scoreboard.score += boss.def.score as usize;
+        commands.entity(entity).despawn();
+    }
+}
@@ -1,6 +1,10 @@
-use crate::{WinSize, BASE_SPEED, FORMATION_MEMBERS_MAX};
+use crate::level::{FormationDef, WaveDef};
+use crate::Playfield;
 use bevy::prelude::{Component, Resource};
-use rand::{thread_rng, Rng};
+use rand::Rng;
+
+// distancia de la fila superior de la rejilla al borde de la pantalla
+const GRID_TOP_MARGIN: f32 = 120.;
 
 /// Component - Formación de enemigos (por enemigo)
 #[derive(Clone, Component)]
@@ -12,6 +16,89 @@ pub struct Formation {
     pub angle: f32, // cambia por tiempo
 }
 
+impl Formation {
+    /// posición actual en la órbita
+    pub fn position(&self) -> (f32, f32) {
+        (
+            self.radius.0 * self.angle.cos() + self.pivot.0,
+            self.radius.1 * self.angle.sin() + self.pivot.1,
+        )
+    }
+}
+
+/// Component - Posición de un enemigo dentro de la rejilla
+#[derive(Clone, Component)]
+pub struct GridSlot {
+    pub row: u32,
+    pub column: u32,
+    pub home: (f32, f32), // posición sin desplazamiento de la marcha
+}
+
+/// Resource - Marcha compartida por todos los enemigos de la rejilla
+#[derive(Default, Resource)]
+pub struct GridMarch {
+    pub offset: (f32, f32),
+    pub dir: f32, // 1. derecha, -1. izquierda
+    pub speed: f32,
+    pub max_speed: f32,
+    pub step_down: f32,
+    pub total: u32, // enemigos al crear la rejilla
+}
+
+impl GridMarch {
+    /// velocidad actual: acelera a medida que se destruyen enemigos
+    pub fn current_speed(&self, alive: usize) -> f32 {
+        if self.total == 0 {
+            return self.speed;
+        }
+
+        let destroyed = 1. - alive as f32 / self.total as f32;
+        self.speed + (self.max_speed - self.speed) * destroyed
+    }
+}
+
+/// crear la rejilla de una oleada: devuelve la marcha y la posición de cada enemigo
+pub fn make_grid(playfield: &Playfield, wave: &WaveDef) -> (GridMarch, Vec<GridSlot>) {
+    let FormationDef::Grid {
+        rows,
+        columns,
+        spacing,
+        speed,
+        max_speed,
+        step_down,
+    } = wave.formation
+    else {
+        unreachable!("make_grid solo acepta oleadas en rejilla");
+    };
+
+    // centrar las columnas y colocar la fila superior cerca del borde
+    let x_start = -(columns.saturating_sub(1) as f32) * spacing.0 / 2.;
+    let y_start = playfield.rect.max.y - GRID_TOP_MARGIN;
+
+    let slots = (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (row, column)))
+        .map(|(row, column)| GridSlot {
+            row,
+            column,
+            home: (
+                x_start + column as f32 * spacing.0,
+                y_start - row as f32 * spacing.1,
+            ),
+        })
+        .collect();
+
+    let march = GridMarch {
+        offset: (0., 0.),
+        dir: 1.,
+        speed,
+        max_speed,
+        step_down,
+        total: rows * columns,
+    };
+
+    (march, slots)
+}
+
 /// Resource - Creación de formaciones
 #[derive(Default)]
 #[derive(Resource)]
@@ -22,10 +109,10 @@ pub struct FormationMaker {
 
 /// Implementación de creación de formaciones
 impl FormationMaker {
-    pub fn make(&mut self, win_size: &WinSize) -> Formation {
+    pub fn make(&mut self, playfield: &Playfield, rng: &mut impl Rng, wave: &WaveDef) -> Formation {
         match (
             &self.current_template,
-            self.current_members >= FORMATION_MEMBERS_MAX,
+            self.current_members >= wave.formation_members,
         ) {
             // si no hay plantilla, se crea una nueva
             (Some(tmpl), false) => {
@@ -35,29 +122,38 @@ impl FormationMaker {
 
             // si la primera formación o anterior esta llena, se crea una nueva
             (None, _) | (_, true) => {
-                let mut rng = thread_rng();
+                let FormationDef::Orbit {
+                    radius_x,
+                    radius_y,
+                    speed,
+                } = wave.formation
+                else {
+                    unreachable!("las rejillas no usan FormationMaker");
+                };
 
                 // computar el inicio x/y
-                let w_span = win_size.w / 2. + 100.;
-                let h_span = win_size.h / 2. + 100.;
+                let w_span = playfield.rect.max.x + 100.;
+                let h_span = playfield.rect.max.y + 100.;
                 let x = if rng.gen_bool(0.5) { w_span } else { -w_span };
                 let y = rng.gen_range(-h_span..h_span);
                 let start = (x, y);
 
                 // computar el pivot x/y
-                let w_span = win_size.w / 4.;
-                let h_span = win_size.h / 3. + 50.0;
+                let w_span = playfield.rect.width() / 4.;
+                let h_span = playfield.rect.height() / 3. + 50.0;
                 let pivot = (rng.gen_range(-w_span..w_span), rng.gen_range(0.0..h_span));
 
-                // computar el radio x/y
-                let radius = (rng.gen_range(80.0..150.), 100.);
+                // computar el radio x/y (un rango vacío es un radio fijo)
+                let radius_x = if radius_x.0 < radius_x.1 {
+                    rng.gen_range(radius_x.0..radius_x.1)
+                } else {
+                    radius_x.0
+                };
+                let radius = (radius_x, radius_y);
 
                 // computar el ángulo inicial
                 let angle = (y - pivot.1).atan2(x - pivot.0);
 
-                // computar la velocidad
-                let speed = BASE_SPEED;
-
                 // crear la formación
                 let formation = Formation {
                     start,
@@ -1,88 +1,295 @@
-use self::formation::{Formation, FormationMaker};
-use crate::components::{Enemy, FromEnemy, Laser, Movable, SpriteSize, Velocity};
+use self::boss::{
+    boss_death_system, boss_fire_system, boss_movement_system, boss_phase_system,
+    boss_spawn_system, Boss, BossPart,
+};
+use self::formation::{make_grid, Formation, FormationMaker, GridMarch, GridSlot};
+use self::path::FlightPath;
+use self::pattern::{bullet_emitter_system, BulletEmitter};
+use self::wave::{wave_advance_system, WaveState};
+use crate::collision::{layer, Collider, Hitbox};
+use crate::components::{DamageFlash, Enemy, EnemyStats, Health, Interpolated, Player, SpriteSize};
+use crate::level::{CurrentLevel, EnemyDef, FormationDef, Level, MovementStyle, WaveDef};
+use crate::rng::GameRng;
+use crate::state::{despawn_with, GameState};
 use crate::{
-    EnemyCount, GameTextures, WinSize, ENEMY_LASER_SIZE, ENEMY_MAX, ENEMY_SIZE, SPRITE_SCALE,
-    TIME_STEP,
+    EnemyCount, GameTextures, LogicSet, Playfield, SimulationSet, SimulationTime, PLAYER_SIZE,
+    SPRITE_SCALE, TIME_STEP,
 };
 
+use bevy::ecs::system::EntityCommands;
+use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
-use rand::{thread_rng, Rng};
+use bevy::utils::HashMap;
+use rand::Rng;
 use std::f32::consts::PI;
 
-mod formation;
+// margen entre la rejilla y los bordes de la pantalla
+const GRID_EDGE_MARGIN: f32 = 20.;
+
+pub mod boss;
+pub mod formation;
+pub mod path;
+pub mod pattern;
+pub mod wave;
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(FormationMaker::default())
-            .add_systems(Update, enemy_spawn_system)
-            .add_systems(Update, enemy_fire_system.run_if(enemy_fire_criteria))
-            .add_systems(Update, enemy_movement_system);
+            .init_resource::<GridMarch>()
+            .init_resource::<WaveState>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    (
+                        boss_death_system,
+                        wave_advance_system,
+                        enemy_spawn_system,
+                        boss_spawn_system,
+                        enemy_fire_system,
+                        enemy_dive_system,
+                        boss_phase_system,
+                        boss_fire_system,
+                        bullet_emitter_system,
+                    )
+                        .chain()
+                        .in_set(LogicSet::Enemy),
+                    (
+                        enemy_movement_system,
+                        enemy_grid_march_system,
+                        enemy_path_system,
+                        boss_movement_system,
+                    )
+                        .chain()
+                        .in_set(SimulationSet::Movement),
+                ),
+            )
+            .add_systems(
+                OnEnter(GameState::Menu),
+                (despawn_with::<Enemy>, despawn_with::<Boss>, enemy_reset_system),
+            );
     }
 }
 
+fn enemy_reset_system(mut commands: Commands, current_level: Res<CurrentLevel>) {
+    commands.insert_resource(FormationMaker::default());
+    commands.insert_resource(GridMarch::default());
+    commands.insert_resource(WaveState::new(&current_level.0));
+    commands.insert_resource(EnemyCount(0));
+}
+
+#[allow(clippy::too_many_arguments)]
 fn enemy_spawn_system(
     mut commands: Commands,
     game_textures: Res<GameTextures>,
     mut enemy_count: ResMut<EnemyCount>,
     mut formation_maker: ResMut<FormationMaker>,
-    win_size: Res<WinSize>,
+    mut wave_state: ResMut<WaveState>,
+    mut game_rng: ResMut<GameRng>,
+    current_level: Res<CurrentLevel>,
+    sim_time: Res<SimulationTime>,
+    playfield: Res<Playfield>,
 ) {
-    if enemy_count.0 < ENEMY_MAX {
+    let Some(wave) = wave_state.current(&current_level.0) else {
+        return;
+    };
+
+    let now = sim_time.elapsed_seconds_f64();
+    if now < wave_state.next_spawn {
+        return;
+    }
+
+    // las rejillas aparecen completas de una vez
+    if let FormationDef::Grid { .. } = wave.formation {
+        if wave_state.spawned == 0 {
+            let (march, slots) = make_grid(&playfield, wave);
+            for slot in slots {
+                // las columnas de la derecha entran por la derecha
+                let entry = entry_path(&current_level.0, wave, slot.home.0 > 0.);
+                let (x, y) = slot.home;
+                let offset = entry.as_ref().map_or(Vec2::ZERO, FlightPath::offset);
+                let translation = Vec3::new(x + offset.x, y + offset.y, 10.);
+
+                // el tipo de enemigo depende de la fila
+                let archetype = current_level.0.archetype(wave.enemy(slot.row));
+                let mut enemy = spawn_enemy(&mut commands, &game_textures, &archetype, translation);
+                enemy.insert(slot);
+
+                if let Some(entry) = entry {
+                    enemy.insert(entry);
+                }
+            }
+
+            enemy_count.0 += march.total;
+            wave_state.spawned = march.total;
+            commands.insert_resource(march);
+        }
+        return;
+    }
+
+    if wave_state.spawned < wave.count && enemy_count.0 < wave.max_alive {
         // obtener formación e iniciar x/y
-        let formation = formation_maker.make(&win_size);
-        let (x, y) = formation.start;
-
-        commands
-            .spawn(SpriteBundle {
-                texture: game_textures.enemy.clone(),
-                transform: Transform {
-                    translation: Vec3::new(x, y, 10.),
-                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                    ..Default::default()
-                },
-                ..Default::default()
-            })
-            .insert(Enemy)
-            .insert(formation)
-            .insert(SpriteSize::from(ENEMY_SIZE));
+        let formation = formation_maker.make(&playfield, &mut *game_rng, wave);
+
+        // con trayectoria de entrada se aparece al inicio de la curva, si no en el borde
+        let entry = entry_path(&current_level.0, wave, formation.start.0 > 0.);
+        let (x, y) = match &entry {
+            Some(entry) => {
+                let (x, y) = formation.position();
+                let offset = entry.offset();
+                (x + offset.x, y + offset.y)
+            }
+            None => formation.start,
+        };
+        let translation = Vec3::new(x, y, 10.);
+
+        let archetype = current_level.0.archetype(wave.enemy(wave_state.spawned));
+        let mut enemy = spawn_enemy(&mut commands, &game_textures, &archetype, translation);
+        enemy.insert(formation);
+
+        if let Some(entry) = entry {
+            enemy.insert(entry);
+        }
 
         enemy_count.0 += 1;
+        wave_state.spawned += 1;
+        wave_state.next_spawn = now + wave.spawn_delay as f64;
     }
 }
 
-fn enemy_fire_criteria() -> bool {
-    thread_rng().gen_bool(1. / 60.)
+// crear un enemigo con el sprite, la hitbox y las estadísticas de su tipo
+fn spawn_enemy<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    game_textures: &GameTextures,
+    archetype: &EnemyDef,
+    translation: Vec3,
+) -> EntityCommands<'w, 's, 'a> {
+    let texture = game_textures
+        .enemies
+        .get(&archetype.sprite)
+        .cloned()
+        .unwrap_or_else(|| game_textures.enemy.clone());
+    let color = archetype
+        .color
+        .map_or(Color::WHITE, |(r, g, b)| Color::rgb(r, g, b));
+
+    let mut enemy = commands.spawn(SpriteBundle {
+        sprite: Sprite { color, ..default() },
+        texture,
+        transform: Transform {
+            translation,
+            scale: Vec3::new(archetype.scale, archetype.scale, 1.),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    enemy
+        .insert(Enemy)
+        .insert(Interpolated::from(translation))
+        .insert(SpriteSize::from(archetype.size))
+        .insert(Collider::new(layer::ENEMY, layer::PLAYER_LASER))
+        .insert(Health::new(archetype.hp))
+        .insert(DamageFlash::default())
+        .insert(EnemyStats {
+            score: archetype.score,
+            movement: archetype.movement,
+        })
+        .insert(BulletEmitter::new(archetype.weapon));
+    if let Some(shape) = archetype.hitbox.clone() {
+        enemy.insert(Hitbox(shape));
+    }
+    enemy
 }
 
+// trayectoria de entrada de la oleada, reflejada para entrar por la derecha
+fn entry_path(level: &Level, wave: &WaveDef, mirror: bool) -> Option<FlightPath> {
+    let name = wave.entry_path.as_ref()?;
+    level.paths.get(name).map(|def| FlightPath::entry(def, mirror))
+}
+
+// enemigos de las oleadas; los jefes disparan con `boss_fire_system`
+type WaveEnemy = (With<Enemy>, Without<BossPart>);
+
 fn enemy_fire_system(
+    mut game_rng: ResMut<GameRng>,
+    current_level: Res<CurrentLevel>,
+    wave_state: Res<WaveState>,
+    slot_query: Query<&GridSlot, With<Enemy>>,
+    mut enemy_query: Query<(&mut BulletEmitter, Option<&GridSlot>), WaveEnemy>,
+) {
+    let Some(wave) = wave_state.current(&current_level.0) else {
+        return;
+    };
+
+    // cada enemigo espera su propio tiempo, más corto con la cadencia y la dificultad
+    let delta = TIME_STEP * wave.fire_rate * wave_state.difficulty();
+
+    // en la rejilla solo dispara el invasor más bajo de cada columna, como en el arcade
+    let mut front: HashMap<u32, u32> = HashMap::default();
+    for slot in slot_query.iter() {
+        let row = front.entry(slot.column).or_insert(slot.row);
+        *row = (*row).max(slot.row);
+    }
+
+    for (mut emitter, slot) in enemy_query.iter_mut() {
+        let Some(weapon) = emitter.weapon else {
+            continue;
+        };
+
+        if let Some(slot) = slot {
+            if front.get(&slot.column) != Some(&slot.row) {
+                continue;
+            }
+        }
+
+        if emitter.tick_cooldown(delta, &weapon, &mut *game_rng) {
+            emitter.trigger(None);
+        }
+    }
+}
+
+// enemigos en su formación, sin trayectoria de entrada o picado en curso
+type InFormation = (With<Enemy>, Without<FlightPath>);
+
+fn enemy_dive_system(
     mut commands: Commands,
-    game_textures: Res<GameTextures>,
-    enemy_query: Query<&Transform, With<Enemy>>,
+    mut game_rng: ResMut<GameRng>,
+    current_level: Res<CurrentLevel>,
+    wave_state: Res<WaveState>,
+    player_query: Query<&Transform, With<Player>>,
+    enemy_query: Query<(Entity, &Transform, &EnemyStats), (InFormation, Without<BossPart>)>,
 ) {
-    for &tf in enemy_query.iter() {
-        let (x, y) = (tf.translation.x, tf.translation.y);
-        // añadir laser enemigo
-        commands
-            .spawn(SpriteBundle {
-                texture: game_textures.enemy_laser.clone(),
-                transform: Transform {
-                    translation: Vec3::new(x, y - 15., 1.), // eje z = profundidad, debe ser 1 para sobrepasar el background
-                    rotation: Quat::from_rotation_x(PI),
-                    scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                },
-                ..Default::default()
-            })
-            .insert(Laser)
-            .insert(SpriteSize::from(ENEMY_LASER_SIZE))
-            .insert(FromEnemy)
-            .insert(Movable { auto_despawn: true })
-            .insert(Velocity { x: 0., y: -1. });
+    let level = &current_level.0;
+    let Some(dive) = wave_state.current(level).and_then(|wave| wave.dive.as_ref()) else {
+        return;
+    };
+
+    let probability = (dive.rate * wave_state.difficulty() * TIME_STEP).clamp(0., 1.);
+    if !game_rng.gen_bool(probability as f64) {
+        return;
+    }
+
+    // elegir un enemigo que pueda picar y no esté ya siguiendo una trayectoria
+    let candidates: Vec<(Entity, &Transform)> = enemy_query
+        .iter()
+        .filter(|(_, _, stats)| stats.movement == MovementStyle::Diver)
+        .map(|(entity, tf, _)| (entity, tf))
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+    let (entity, tf) = candidates[game_rng.gen_range(0..candidates.len())];
+
+    // los puntos con x positiva van hacia el jugador
+    let player_x = player_query.get_single().map_or(0., |player_tf| player_tf.translation.x);
+    let mirror = player_x < tf.translation.x;
+    if let Some(def) = level.paths.get(&dive.path) {
+        commands.entity(entity).insert(FlightPath::dive(def, mirror));
     }
 }
 
-fn enemy_movement_system(mut query: Query<(&mut Transform, &mut Formation), With<Enemy>>) {
+fn enemy_movement_system(mut query: Query<(&mut Transform, &mut Formation), InFormation>) {
     for (mut transform, mut formation) in query.iter_mut() {
         // posicion inicial
         let (x_org, y_org) = (transform.translation.x, transform.translation.y);
@@ -128,3 +335,89 @@ fn enemy_movement_system(mut query: Query<(&mut Transform, &mut Formation), With
         (translation.x, translation.y) = (x, y);
     }
 }
+
+fn enemy_grid_march_system(
+    mut march: ResMut<GridMarch>,
+    mut next_state: ResMut<NextState<GameState>>,
+    playfield: Res<Playfield>,
+    mut query: Query<(&mut Transform, &GridSlot, &SpriteSize, Option<&FlightPath>), With<Enemy>>,
+) {
+    // los enemigos en picado cuentan para los límites pero no se mueven con la rejilla
+    let alive = query.iter().count();
+    if alive == 0 {
+        return;
+    }
+
+    // caja de los enemigos restantes sin la marcha, con el tamaño de cada tipo
+    let (min_x, max_x, min_y) = query.iter().fold(
+        (f32::MAX, f32::MIN, f32::MAX),
+        |(min_x, max_x, min_y), (transform, slot, size, _)| {
+            let half_size = size.0 * transform.scale.xy() / 2.;
+            (
+                min_x.min(slot.home.0 - half_size.x),
+                max_x.max(slot.home.0 + half_size.x),
+                min_y.min(slot.home.1 - half_size.y),
+            )
+        },
+    );
+
+    let limit = playfield.rect.max.x - GRID_EDGE_MARGIN;
+    let dx = march.dir * march.current_speed(alive) * TIME_STEP;
+
+    // al tocar un borde la rejilla baja y cambia de dirección
+    if max_x + march.offset.0 + dx > limit || min_x + march.offset.0 + dx < -limit {
+        march.dir = -march.dir;
+        march.offset.1 -= march.step_down;
+    } else {
+        march.offset.0 += dx;
+    }
+
+    // si la rejilla llega a la fila del jugador los invasores aterrizan, como en el arcade
+    if min_y + march.offset.1 <= playfield.rect.min.y + PLAYER_SIZE.1 * SPRITE_SCALE {
+        next_state.set(GameState::GameOver);
+    }
+
+    for (mut transform, slot, _, path) in query.iter_mut() {
+        if path.is_some() {
+            continue;
+        }
+
+        let translation = &mut transform.translation;
+        (translation.x, translation.y) = (slot.home.0 + march.offset.0, slot.home.1 + march.offset.1);
+    }
+}
+
+// enemigo en una trayectoria, relativa a su formación o a su puesto en la rejilla
+type PathFollower = (
+    Entity,
+    &'static mut Transform,
+    &'static mut FlightPath,
+    Option<&'static Formation>,
+    Option<&'static GridSlot>,
+);
+
+fn enemy_path_system(
+    mut commands: Commands,
+    march: Res<GridMarch>,
+    mut query: Query<PathFollower, With<Enemy>>,
+) {
+    for (entity, mut transform, mut path, formation, slot) in query.iter_mut() {
+        // la trayectoria es relativa a la posición actual en la formación
+        let (x_target, y_target) = match (formation, slot) {
+            (Some(formation), _) => formation.position(),
+            (None, Some(slot)) => (slot.home.0 + march.offset.0, slot.home.1 + march.offset.1),
+            (None, None) => continue,
+        };
+
+        let done = path.advance(TIME_STEP);
+        let offset = path.offset();
+
+        let translation = &mut transform.translation;
+        (translation.x, translation.y) = (x_target + offset.x, y_target + offset.y);
+
+        // al terminar se vuelve al movimiento de la formación
+        if done {
+            commands.entity(entity).remove::<FlightPath>();
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use crate::level::{PathDef, SplineKind};
+
+use bevy::prelude::*;
+
+// muestras usadas para aproximar la longitud de una curva
+const LENGTH_SAMPLES: usize = 32;
+
+/// Component - Trayectoria del enemigo hacia su posición en la formación (entrada o picado)
+#[derive(Component)]
+pub struct FlightPath {
+    kind: SplineKind,
+    points: Vec<Vec2>, // relativos a la posición en la formación
+    speed: f32,
+    length: f32,
+    t: f32, // progreso de 0 a 1
+}
+
+impl FlightPath {
+    /// trayectoria de entrada: termina en la posición de la formación
+    pub fn entry(def: &PathDef, mirror: bool) -> Self {
+        let mut points = def_points(def, mirror);
+        points.push(Vec2::ZERO);
+        Self::new(def, points)
+    }
+
+    /// picado: sale de la posición de la formación y vuelve a ella
+    pub fn dive(def: &PathDef, mirror: bool) -> Self {
+        let mut points = vec![Vec2::ZERO];
+        points.extend(def_points(def, mirror));
+        points.push(Vec2::ZERO);
+        Self::new(def, points)
+    }
+
+    fn new(def: &PathDef, points: Vec<Vec2>) -> Self {
+        let length = (1..=LENGTH_SAMPLES)
+            .map(|i| {
+                let t0 = (i - 1) as f32 / LENGTH_SAMPLES as f32;
+                let t1 = i as f32 / LENGTH_SAMPLES as f32;
+                sample(def.kind, &points, t0).distance(sample(def.kind, &points, t1))
+            })
+            .sum();
+
+        Self {
+            kind: def.kind,
+            points,
+            speed: def.speed,
+            length,
+            t: 0.,
+        }
+    }
+
+    /// avanzar `delta` segundos, devuelve true al llegar al final
+    pub fn advance(&mut self, delta: f32) -> bool {
+        let step = if self.length > 0. {
+            self.speed * delta / self.length
+        } else {
+            1.
+        };
+        self.t = (self.t + step).min(1.);
+        self.t >= 1.
+    }
+
+    /// posición actual relativa a la formación
+    pub fn offset(&self) -> Vec2 {
+        sample(self.kind, &self.points, self.t)
+    }
+}
+
+// puntos del fichero, reflejados en el eje x si hace falta
+fn def_points(def: &PathDef, mirror: bool) -> Vec<Vec2> {
+    let dir = if mirror { -1. } else { 1. };
+    def.points
+        .iter()
+        .map(|&(x, y)| Vec2::new(x * dir, y))
+        .collect()
+}
+
+/// punto de la curva en `t` (de 0 a 1)
+pub fn sample(kind: SplineKind, points: &[Vec2], t: f32) -> Vec2 {
+    match points {
+        [] => Vec2::ZERO,
+        [point] => *point,
+        _ => match kind {
+            SplineKind::Bezier => bezier(points, t),
+            SplineKind::CatmullRom => catmull_rom(points, t),
+        },
+    }
+}
+
+// curva de Bézier de grado n (algoritmo de De Casteljau)
+fn bezier(points: &[Vec2], t: f32) -> Vec2 {
+    let mut points = points.to_vec();
+    for n in (1..points.len()).rev() {
+        for i in 0..n {
+            points[i] = points[i].lerp(points[i + 1], t);
+        }
+    }
+    points[0]
+}
+
+// Catmull-Rom uniforme: pasa por todos los puntos
+fn catmull_rom(points: &[Vec2], t: f32) -> Vec2 {
+    let segments = points.len() - 1;
+    let s = t.clamp(0., 1.) * segments as f32;
+    let i = (s as usize).min(segments - 1);
+    let u = s - i as f32;
+
+    // repetir los extremos como puntos de control
+    let p0 = points[i.saturating_sub(1)];
+    let p1 = points[i];
+    let p2 = points[i + 1];
+    let p3 = points[(i + 2).min(segments)];
+
+    0.5 * (2. * p1
+        + (p2 - p0) * u
+        + (2. * p0 - 5. * p1 + 4. * p2 - p3) * u * u
+        + (3. * p1 - p0 - 3. * p2 + p3) * u * u * u)
+}
@@ -0,0 +1,164 @@
+use crate::collision::{layer, Collider, Hitbox, Shape};
+use crate::components::{Boundary, Damage, FromEnemy, Interpolated, Laser, Movable, Player, SpriteSize, Velocity};
+use crate::level::{BulletPattern, WeaponDef};
+use crate::{GameTextures, ENEMY_LASER_HITBOX, ENEMY_LASER_SIZE, SPRITE_SCALE, TIME_STEP};
+
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::{PI, TAU};
+
+/// Component - Emisor de disparos del enemigo: ráfagas en curso y giro de las espirales
+#[derive(Component, Default)]
+pub struct BulletEmitter {
+    pub weapon: Option<WeaponDef>, // arma propia del tipo de enemigo
+    active: Option<WeaponDef>,     // arma de la ráfaga en curso
+    volleys_left: u32,
+    timer: f32,
+    angle: f32,            // giro acumulado de las espirales
+    cooldown: Option<f32>, // segundos hasta el siguiente disparo, sorteado al empezar
+}
+
+impl BulletEmitter {
+    pub fn new(weapon: Option<WeaponDef>) -> Self {
+        Self {
+            weapon,
+            ..default()
+        }
+    }
+
+    pub fn is_firing(&self) -> bool {
+        self.volleys_left > 0
+    }
+
+    /// descontar `delta` de la espera entre disparos de `weapon`, devuelve true al agotarse
+    pub fn tick_cooldown(&mut self, delta: f32, weapon: &WeaponDef, rng: &mut impl Rng) -> bool {
+        // la primera espera también es aleatoria para escalonar los disparos
+        let cooldown = match self.cooldown {
+            Some(cooldown) => cooldown - delta,
+            None => roll_cooldown(weapon, rng),
+        };
+
+        if cooldown > 0. {
+            self.cooldown = Some(cooldown);
+            return false;
+        }
+
+        self.cooldown = Some(roll_cooldown(weapon, rng));
+        true
+    }
+
+    /// empezar una ráfaga con `weapon` o con el arma propia; se ignora si ya hay una en curso
+    pub fn trigger(&mut self, weapon: Option<WeaponDef>) {
+        if self.is_firing() {
+            return;
+        }
+
+        if let Some(weapon) = weapon.or(self.weapon) {
+            self.active = Some(weapon);
+            self.volleys_left = weapon.burst.max(1);
+            self.timer = 0.;
+        }
+    }
+}
+
+fn roll_cooldown(weapon: &WeaponDef, rng: &mut impl Rng) -> f32 {
+    let (min, max) = weapon.cooldown;
+    if min < max {
+        rng.gen_range(min..max)
+    } else {
+        min
+    }
+}
+
+// ángulos de una descarga (0 es hacia abajo, positivo hacia la derecha)
+fn volley_angles(pattern: BulletPattern, aim: f32, rotation: f32) -> Vec<f32> {
+    let fan = |shots: u32, spread: f32, center: f32| {
+        (0..shots)
+            .map(|shot| center + (shot as f32 - (shots.max(1) - 1) as f32 / 2.) * spread)
+            .collect()
+    };
+    let ring = |shots: u32, offset: f32| {
+        (0..shots)
+            .map(|shot| offset + shot as f32 * TAU / shots as f32)
+            .collect()
+    };
+
+    match pattern {
+        BulletPattern::Straight => vec![0.],
+        BulletPattern::Spread { shots, spread } => fan(shots, spread, 0.),
+        BulletPattern::Aimed { shots, spread } => fan(shots, spread, aim),
+        BulletPattern::Ring { shots } => ring(shots, 0.),
+        BulletPattern::Spiral { shots, step: _ } => ring(shots, rotation),
+    }
+}
+
+// avanzar las ráfagas en curso y crear los lasers de cada descarga
+pub fn bullet_emitter_system(
+    mut commands: Commands,
+    game_textures: Res<GameTextures>,
+    player_query: Query<&Transform, With<Player>>,
+    mut emitter_query: Query<(&Transform, &mut BulletEmitter)>,
+) {
+    let player = player_query.get_single().ok().map(|tf| tf.translation);
+
+    for (tf, mut emitter) in emitter_query.iter_mut() {
+        let Some(weapon) = emitter.active.filter(|_| emitter.is_firing()) else {
+            continue;
+        };
+
+        emitter.timer -= TIME_STEP;
+        if emitter.timer > 0. {
+            continue;
+        }
+
+        // apuntar al jugador, o hacia abajo si no está
+        let aim = player.map_or(0., |player| {
+            let dir = player - tf.translation;
+            dir.x.atan2(-dir.y)
+        });
+
+        for angle in volley_angles(weapon.pattern, aim, emitter.angle) {
+            spawn_enemy_laser(&mut commands, &game_textures, tf.translation, angle, &weapon);
+        }
+
+        if let BulletPattern::Spiral { step, .. } = weapon.pattern {
+            emitter.angle = (emitter.angle + step) % TAU;
+        }
+        emitter.volleys_left -= 1;
+        emitter.timer = weapon.burst_interval;
+    }
+}
+
+fn spawn_enemy_laser(
+    commands: &mut Commands,
+    game_textures: &GameTextures,
+    origin: Vec3,
+    angle: f32,
+    weapon: &WeaponDef,
+) {
+    let translation = Vec3::new(origin.x, origin.y - 15., 1.); // eje z = profundidad, debe ser 1 para sobrepasar el background
+
+    // añadir laser enemigo
+    commands
+        .spawn(SpriteBundle {
+            texture: game_textures.enemy_laser.clone(),
+            transform: Transform {
+                translation,
+                rotation: Quat::from_rotation_z(angle) * Quat::from_rotation_x(PI),
+                scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
+            },
+            ..Default::default()
+        })
+        .insert(Laser)
+        .insert(SpriteSize::from(ENEMY_LASER_SIZE))
+        .insert(Hitbox(Shape::Polygon(ENEMY_LASER_HITBOX.to_vec())))
+        .insert(Collider::new(layer::ENEMY_LASER, 0))
+        .insert(FromEnemy)
+        .insert(Damage(weapon.damage))
+        .insert(Interpolated::from(translation))
+        .insert(Movable { boundary: Boundary::Despawn })
+        .insert(Velocity {
+            x: angle.sin() * weapon.speed,
+            y: -angle.cos() * weapon.speed,
+        });
+}
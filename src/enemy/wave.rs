@@ -0,0 +1,70 @@
+use super::formation::FormationMaker;
+use crate::level::{CurrentLevel, Level, WaveDef};
+use crate::{EnemyCount, SimulationTime};
+
+use bevy::prelude::*;
+
+// aumento de la dificultad por cada vuelta completa al nivel
+const DIFFICULTY_PER_LOOP: f32 = 0.25;
+
+/// Resource - Progreso de las oleadas del nivel actual
+#[derive(Resource, Default)]
+pub struct WaveState {
+    pub wave: usize,      // índice de la oleada actual
+    pub loop_count: u32,  // vueltas completas al nivel
+    pub spawned: u32,     // enemigos creados en la oleada actual
+    pub next_spawn: f64,  // tiempo de simulación del siguiente spawn
+    pub boss_spawned: bool, // el jefe de la oleada ya ha aparecido
+}
+
+impl WaveState {
+    pub fn new(level: &Level) -> Self {
+        Self {
+            next_spawn: level.waves.first().map_or(0., |wave| wave.start_delay as f64),
+            ..default()
+        }
+    }
+
+    pub fn current<'a>(&self, level: &'a Level) -> Option<&'a WaveDef> {
+        level.waves.get(self.wave)
+    }
+
+    pub fn difficulty(&self) -> f32 {
+        1. + self.loop_count as f32 * DIFFICULTY_PER_LOOP
+    }
+}
+
+// pasar a la siguiente oleada cuando la actual está completa y sin enemigos
+pub fn wave_advance_system(
+    current_level: Res<CurrentLevel>,
+    mut wave_state: ResMut<WaveState>,
+    mut formation_maker: ResMut<FormationMaker>,
+    enemy_count: Res<EnemyCount>,
+    sim_time: Res<SimulationTime>,
+) {
+    let level = &current_level.0;
+    let Some(wave) = wave_state.current(level) else {
+        return;
+    };
+
+    // las oleadas con jefe terminan al destruirlo
+    let boss_pending = wave.boss.is_some() && !wave_state.boss_spawned;
+    if wave_state.spawned < wave.total() || enemy_count.0 > 0 || boss_pending {
+        return;
+    }
+
+    // al terminar la última oleada se vuelve a la primera con más dificultad
+    wave_state.wave += 1;
+    if wave_state.wave >= level.waves.len() {
+        wave_state.wave = 0;
+        wave_state.loop_count += 1;
+    }
+
+    let start_delay = level.waves[wave_state.wave].start_delay as f64;
+    wave_state.spawned = 0;
+    wave_state.boss_spawned = false;
+    wave_state.next_spawn = sim_time.elapsed_seconds_f64() + start_delay;
+
+    // las formaciones no se comparten entre oleadas
+    *formation_maker = FormationMaker::default();
+}
@@ -0,0 +1,114 @@
+use crate::replay::ReplayMode;
+use crate::rng::GameRng;
+use crate::state::{AutoStart, GameState};
+use crate::{
+    GameTextures, PlayerState, Scoreboard, SimulationTime, SpaceInvadersPlugins, WindowedPlugin,
+    TIME_STEP,
+};
+
+use bevy::asset::AssetPlugin;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+// updates máximos esperando a que carguen los assets y empiece la partida
+const HEADLESS_MAX_LOADING_UPDATES: u64 = 100_000;
+
+/// Resultado de una simulación headless
+#[derive(Debug, PartialEq, Eq)]
+pub struct HeadlessSummary {
+    pub seed: u64,
+    pub ticks: u64,
+    pub score: usize,
+    pub lives: u32,
+    pub game_over: bool,
+}
+
+impl fmt::Display for HeadlessSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "seed: {} ticks: {} score: {} lives: {} game_over: {}",
+            self.seed, self.ticks, self.score, self.lives, self.game_over
+        )
+    }
+}
+
+/// Plugin - Recursos del juego sin ventana, render ni audio (sustituye a `WindowedPlugin`)
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, headless_setup_system);
+    }
+}
+
+/// crear una App sin ventana, render ni audio, con un área de juego virtual fija
+pub fn headless_app(game_rng: GameRng, replay_mode: ReplayMode) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, AssetPlugin::default()))
+        // cada update avanza exactamente un tick de simulación
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+            TIME_STEP,
+        )))
+        .insert_resource(game_rng)
+        .insert_resource(replay_mode)
+        .add_plugins(
+            SpaceInvadersPlugins
+                .build()
+                .disable::<WindowedPlugin>()
+                .add(HeadlessPlugin),
+        )
+        // empezar directamente en la partida, sin menú
+        .insert_resource(AutoStart(true));
+
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// avanzar la simulación hasta `ticks` ticks o hasta el game over
+pub fn run_ticks(app: &mut App, ticks: u64) -> HeadlessSummary {
+    // esperar a que cargue el nivel; estos updates no avanzan la simulación
+    for _ in 0..HEADLESS_MAX_LOADING_UPDATES {
+        if *app.world.resource::<State<GameState>>().get() == GameState::Playing {
+            break;
+        }
+        app.update();
+    }
+
+    for _ in 0..ticks {
+        let sim_tick = app.world.resource::<SimulationTime>().tick;
+        let state = *app.world.resource::<State<GameState>>().get();
+        if sim_tick >= ticks || state == GameState::GameOver {
+            break;
+        }
+        app.update();
+    }
+
+    // una grabación cortada por el límite de ticks también se guarda
+    app.world.resource_mut::<ReplayMode>().save_recording();
+
+    HeadlessSummary {
+        seed: app.world.resource::<GameRng>().seed(),
+        ticks: app.world.resource::<SimulationTime>().tick,
+        score: app.world.resource::<Scoreboard>().score,
+        lives: app.world.resource::<PlayerState>().lives,
+        game_over: *app.world.resource::<State<GameState>>().get() == GameState::GameOver,
+    }
+}
+
+// recursos que normalmente crea `setup_system`, sin cargar assets
+fn headless_setup_system(mut commands: Commands) {
+    commands.insert_resource(GameTextures {
+        player: Handle::default(),
+        player_laser: Handle::default(),
+        enemy: Handle::default(),
+        enemy_laser: Handle::default(),
+        explosion: Handle::default(),
+        enemies: HashMap::new(),
+    });
+}
@@ -0,0 +1,451 @@
+use crate::collision::Shape;
+use crate::state::GameState;
+use crate::{BASE_SPEED, ENEMY_HITBOX, ENEMY_SIZE, ENEMY_SPRITE, SPRITE_SCALE};
+
+use bevy::asset::{AssetLoader, LoadContext, LoadState, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::utils::{BoxedFuture, HashMap};
+use serde::Deserialize;
+
+const LEVEL_FILE: &str = "levels/level_01.level.ron";
+
+// region:      --- Level Definitions ---
+
+/// Asset - Nivel: lista ordenada de oleadas
+#[derive(Deserialize, TypeUuid, TypePath, Clone)]
+#[uuid = "6f2d1c8e-3b0a-4c52-9f0e-5a7d2b1e9c41"]
+pub struct Level {
+    pub name: String,
+    #[serde(default)]
+    pub archetypes: HashMap<String, EnemyDef>, // tipos de enemigo por nombre
+    #[serde(default)]
+    pub paths: HashMap<String, PathDef>, // trayectorias reutilizables por nombre
+    #[serde(default)]
+    pub bosses: HashMap<String, BossDef>,
+    pub waves: Vec<WaveDef>,
+}
+
+/// Tipo de enemigo: aspecto, estadísticas y comportamiento
+#[derive(Deserialize, Clone)]
+pub struct EnemyDef {
+    pub sprite: String,
+    #[serde(default)]
+    pub color: Option<(f32, f32, f32)>, // tinte del sprite
+    pub size: (f32, f32),               // tamaño en píxeles del sprite
+    #[serde(default)]
+    pub hitbox: Option<Shape>, // hitbox precisa; sin ella se usa `size`
+    pub scale: f32,
+    pub hp: u32,
+    pub score: u32,
+    pub movement: MovementStyle,
+    #[serde(default)]
+    pub weapon: Option<WeaponDef>,
+}
+
+/// Comportamiento del enemigo además de su formación
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MovementStyle {
+    Formation, // no sale de la formación
+    Diver,     // hace picados si la oleada los tiene
+}
+
+/// Arma del enemigo: patrón de disparo y ráfagas
+#[derive(Deserialize, Clone, Copy)]
+pub struct WeaponDef {
+    pub pattern: BulletPattern,
+    pub speed: f32, // velocidad del laser relativa a `BASE_SPEED`
+    #[serde(default = "default_damage")]
+    pub damage: u32,
+    #[serde(default = "default_burst")]
+    pub burst: u32, // descargas seguidas en cada disparo
+    #[serde(default)]
+    pub burst_interval: f32, // segundos entre descargas de la ráfaga
+    #[serde(default = "default_cooldown")]
+    pub cooldown: (f32, f32), // rango de segundos entre disparos de cada enemigo
+}
+
+/// Patrón de una descarga (ángulos en radianes)
+#[derive(Deserialize, Clone, Copy)]
+pub enum BulletPattern {
+    /// un laser hacia abajo
+    Straight,
+    /// abanico hacia abajo
+    Spread { shots: u32, spread: f32 },
+    /// abanico apuntado al jugador
+    Aimed { shots: u32, spread: f32 },
+    /// anillo de lasers en todas las direcciones
+    Ring { shots: u32 },
+    /// anillo que gira `step` en cada descarga
+    Spiral { shots: u32, step: f32 },
+}
+
+fn default_damage() -> u32 {
+    1
+}
+
+fn default_burst() -> u32 {
+    1
+}
+
+fn default_cooldown() -> (f32, f32) {
+    (2., 4.)
+}
+
+impl Default for EnemyDef {
+    /// enemigo usado si la oleada no indica tipos
+    fn default() -> Self {
+        Self {
+            sprite: ENEMY_SPRITE.into(),
+            color: None,
+            size: ENEMY_SIZE,
+            hitbox: Some(Shape::Polygon(ENEMY_HITBOX.to_vec())),
+            scale: SPRITE_SCALE,
+            hp: 1,
+            score: 1,
+            movement: MovementStyle::Diver,
+            weapon: Some(WeaponDef {
+                pattern: BulletPattern::Straight,
+                speed: 1.,
+                damage: default_damage(),
+                burst: default_burst(),
+                burst_interval: 0.,
+                cooldown: default_cooldown(),
+            }),
+        }
+    }
+}
+
+/// Definición de una oleada de enemigos
+/// (`count`, `max_alive`, `formation_members` y `spawn_delay` no se usan en las rejillas)
+#[derive(Deserialize, Clone)]
+pub struct WaveDef {
+    #[serde(default)]
+    pub count: u32, // enemigos totales de la oleada
+    #[serde(default)]
+    pub max_alive: u32, // enemigos simultáneos en pantalla
+    #[serde(default)]
+    pub formation_members: u32, // enemigos por formación
+    pub formation: FormationDef,
+    #[serde(default)]
+    pub start_delay: f32, // segundos antes del primer spawn
+    #[serde(default)]
+    pub spawn_delay: f32, // segundos entre spawns
+    pub fire_rate: f32,   // multiplicador de la cadencia de disparo de la oleada
+    #[serde(default)]
+    pub entry_path: Option<String>, // trayectoria de entrada a la formación
+    #[serde(default)]
+    pub dive: Option<DiveDef>,
+    #[serde(default)]
+    pub enemies: Vec<String>, // tipos de enemigo, por orden de aparición o por filas
+    #[serde(default)]
+    pub boss: Option<String>, // jefe que aparece al eliminar la oleada
+}
+
+/// Jefe formado por varias partes con su propia hitbox
+#[derive(Deserialize, Clone)]
+pub struct BossDef {
+    pub parts: Vec<BossPartDef>,
+    pub phases: Vec<BossPhaseDef>,
+    pub score: u32, // puntos extra al destruir el núcleo
+}
+
+/// Parte de un jefe: un tipo de enemigo colocado respecto al centro del jefe
+#[derive(Deserialize, Clone)]
+pub struct BossPartDef {
+    pub archetype: String,
+    pub offset: (f32, f32),
+    #[serde(default)]
+    pub core: bool, // al destruir el núcleo se destruye el jefe
+}
+
+/// Fase de un jefe, activa cuando su vida baja de `health`
+#[derive(Deserialize, Clone)]
+pub struct BossPhaseDef {
+    pub health: f32, // fracción de la vida total (de 0 a 1)
+    pub movement: BossMovement,
+    pub fire_rate: f32, // multiplicador de la cadencia de disparo
+    #[serde(default)]
+    pub weapon: Option<WeaponDef>, // sustituye al arma de todas las partes
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum BossMovement {
+    /// curva de Lissajous alrededor de su posición inicial
+    Hover { amplitude: (f32, f32), frequency: f32 },
+    /// de un borde al otro de la pantalla
+    Sweep { speed: f32 },
+}
+
+/// Picados hacia el jugador durante una oleada
+#[derive(Deserialize, Clone)]
+pub struct DiveDef {
+    pub path: String,
+    pub rate: f32, // picados por segundo
+}
+
+/// Trayectoria definida por puntos relativos a la posición en la formación
+/// (las entradas se escriben desde la izquierda y los picados con x positiva hacia el jugador)
+#[derive(Deserialize, Clone)]
+pub struct PathDef {
+    pub kind: SplineKind,
+    pub points: Vec<(f32, f32)>,
+    pub speed: f32,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum SplineKind {
+    Bezier,
+    CatmullRom,
+}
+
+/// Parámetros de las formaciones de una oleada
+#[derive(Deserialize, Clone)]
+pub enum FormationDef {
+    /// órbita elíptica alrededor de un pivot aleatorio
+    Orbit {
+        radius_x: (f32, f32), // rango del radio horizontal
+        radius_y: f32,
+        speed: f32,
+    },
+    /// rejilla clásica que marcha de lado a lado y baja en los bordes
+    Grid {
+        rows: u32,
+        columns: u32,
+        spacing: (f32, f32), // distancia entre enemigos x/y
+        speed: f32,          // velocidad con la rejilla completa
+        max_speed: f32,      // velocidad con un solo enemigo restante
+        step_down: f32,      // descenso al tocar un borde
+    },
+}
+
+impl Level {
+    /// tipo de enemigo por nombre, o el tipo por defecto
+    pub fn archetype(&self, name: Option<&str>) -> EnemyDef {
+        name.and_then(|name| self.archetypes.get(name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// comprobar que las oleadas solo usan trayectorias, tipos y jefes definidos, y que
+    /// sus parámetros no bloquean la partida
+    pub fn validate(&self) -> Result<(), bevy::asset::Error> {
+        for boss in self.waves.iter().filter_map(|wave| wave.boss.as_ref()) {
+            let Some(def) = self.bosses.get(boss) else {
+                return Err(bevy::asset::Error::msg(format!("jefe no definido: {boss}")));
+            };
+            if def.parts.iter().filter(|part| part.core).count() != 1 || def.phases.is_empty() {
+                return Err(bevy::asset::Error::msg(format!(
+                    "el jefe {boss} necesita un núcleo y al menos una fase"
+                )));
+            }
+        }
+
+        let parts = self.bosses.values().flat_map(|boss| boss.parts.iter());
+        let enemies = self
+            .waves
+            .iter()
+            .flat_map(|wave| wave.enemies.iter())
+            .chain(parts.map(|part| &part.archetype));
+        for enemy in enemies {
+            if !self.archetypes.contains_key(enemy) {
+                return Err(bevy::asset::Error::msg(format!("tipo de enemigo no definido: {enemy}")));
+            }
+        }
+
+        for (name, archetype) in self.archetypes.iter() {
+            if archetype.hitbox.as_ref().is_some_and(|hitbox| !hitbox.is_valid()) {
+                return Err(bevy::asset::Error::msg(format!(
+                    "hitbox no válida en el tipo {name}: radio no positivo o polígono no convexo"
+                )));
+            }
+        }
+
+        // cadencias y esperas negativas
+        let phases = self.bosses.values().flat_map(|boss| boss.phases.iter());
+        let mut weapons = self
+            .archetypes
+            .values()
+            .filter_map(|archetype| archetype.weapon.as_ref())
+            .chain(phases.clone().filter_map(|phase| phase.weapon.as_ref()));
+        if weapons.any(|weapon| weapon.cooldown.0 < 0. || weapon.cooldown.1 < 0.) {
+            return Err(bevy::asset::Error::msg("cooldown negativo en un arma"));
+        }
+        let fire_rates = self.waves.iter().map(|wave| wave.fire_rate);
+        if fire_rates.chain(phases.map(|phase| phase.fire_rate)).any(|rate| rate < 0.) {
+            return Err(bevy::asset::Error::msg("fire_rate negativo en una oleada o fase"));
+        }
+
+        // las órbitas necesitan count y max_alive: sin ellos la oleada se salta o no
+        // llega a crear enemigos y no termina nunca
+        for (index, wave) in self.waves.iter().enumerate() {
+            if matches!(wave.formation, FormationDef::Orbit { .. })
+                && (wave.count == 0 || wave.max_alive == 0)
+            {
+                return Err(bevy::asset::Error::msg(format!(
+                    "la oleada {index} necesita count y max_alive mayores que cero"
+                )));
+            }
+
+            // el movimiento en órbita divide por el radio menor
+            if let FormationDef::Orbit {
+                radius_x, radius_y, ..
+            } = wave.formation
+            {
+                if radius_x.0 <= 0. || radius_x.1 < radius_x.0 || radius_y <= 0. {
+                    return Err(bevy::asset::Error::msg(format!(
+                        "la oleada {index} necesita radios positivos y radius_x de menor a mayor"
+                    )));
+                }
+            }
+        }
+
+        let paths = self.waves.iter().flat_map(|wave| {
+            let dive = wave.dive.as_ref().map(|dive| &dive.path);
+            wave.entry_path.iter().chain(dive)
+        });
+
+        for path in paths {
+            if !self.paths.contains_key(path) {
+                return Err(bevy::asset::Error::msg(format!("trayectoria no definida: {path}")));
+            }
+        }
+
+        // sin velocidad la trayectoria no termina y el enemigo se queda en su inicio
+        for (name, path) in self.paths.iter() {
+            if path.speed <= 0. {
+                return Err(bevy::asset::Error::msg(format!(
+                    "la trayectoria {name} necesita una velocidad positiva"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl WaveDef {
+    /// enemigos totales de la oleada
+    pub fn total(&self) -> u32 {
+        match self.formation {
+            FormationDef::Orbit { .. } => self.count,
+            FormationDef::Grid { rows, columns, .. } => rows * columns,
+        }
+    }
+
+    /// tipo del enemigo `index` (orden de aparición, o fila en las rejillas)
+    pub fn enemy(&self, index: u32) -> Option<&str> {
+        let len = self.enemies.len();
+        if len == 0 {
+            return None;
+        }
+
+        let index = match self.formation {
+            // repartir los tipos entre las filas, de arriba a abajo
+            FormationDef::Grid { rows, .. } => index as usize * len / rows.max(1) as usize,
+            FormationDef::Orbit { .. } => index as usize % len,
+        };
+        self.enemies.get(index.min(len - 1)).map(String::as_str)
+    }
+}
+
+impl Default for Level {
+    /// nivel usado si no se puede cargar el fichero: una oleada infinita de 2 en 2
+    fn default() -> Self {
+        Self {
+            name: "default".into(),
+            archetypes: HashMap::default(),
+            paths: HashMap::default(),
+            bosses: HashMap::default(),
+            waves: vec![WaveDef {
+                count: u32::MAX,
+                max_alive: 2,
+                formation_members: 2,
+                formation: FormationDef::Orbit {
+                    radius_x: (80., 150.),
+                    radius_y: 100.,
+                    speed: BASE_SPEED,
+                },
+                start_delay: 0.,
+                spawn_delay: 0.,
+                fire_rate: 1.,
+                entry_path: None,
+                dive: None,
+                enemies: Vec::new(),
+                boss: None,
+            }],
+        }
+    }
+}
+
+// endregion:   --- Level Definitions ---
+
+// region:      --- Resources ---
+
+/// Resource - Handle del nivel que se está cargando
+#[derive(Resource)]
+pub struct LevelHandle(pub Handle<Level>);
+
+/// Resource - Nivel en juego, disponible al terminar el estado `Loading`
+#[derive(Resource, Default)]
+pub struct CurrentLevel(pub Level);
+
+// endregion:   --- Resources ---
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let level: Level = ron::de::from_bytes(bytes)?;
+            level.validate()?;
+            load_context.set_default_asset(LoadedAsset::new(level));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Level>()
+            .init_asset_loader::<LevelLoader>()
+            .init_resource::<CurrentLevel>()
+            .add_systems(Startup, level_load_system)
+            .add_systems(Update, level_ready_system.run_if(in_state(GameState::Loading)));
+    }
+}
+
+fn level_load_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(LevelHandle(asset_server.load(LEVEL_FILE)));
+}
+
+// esperar a que cargue el nivel y pasar al menú
+fn level_ready_system(
+    mut current_level: ResMut<CurrentLevel>,
+    mut next_state: ResMut<NextState<GameState>>,
+    asset_server: Res<AssetServer>,
+    levels: Res<Assets<Level>>,
+    level_handle: Res<LevelHandle>,
+) {
+    if let Some(level) = levels.get(&level_handle.0) {
+        current_level.0 = level.clone();
+    } else if asset_server.get_load_state(&level_handle.0) == LoadState::Failed {
+        // usar el nivel por defecto si el fichero no existe o no es válido
+        error!("no se pudo cargar {LEVEL_FILE}, usando el nivel por defecto");
+        current_level.0 = Level::default();
+    } else {
+        return;
+    }
+
+    next_state.set(GameState::Menu);
+}
@@ -0,0 +1,1117 @@
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use components::{
+    Boundary, Damage, DamageFlash, Enemy, EnemyStats, Explosion, ExplosionTimer, ExplosionToSpawn, FromEnemy, FromPlayer, Laser, Movable,
+    Piercing, Background, BossHealthBar, BossHealthFill, Health, Interpolated, LivesText, Player, ScoreText, SpriteSize, Velocity,
+};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use bevy::app::PluginGroupBuilder;
+use bunker::BunkerPlugin;
+use cli::CliArgs;
+use collision::{collision_grid_system, collision_system, layer, CollisionEvent, CollisionGrid, Shape};
+use enemy::boss::Boss;
+use enemy::EnemyPlugin;
+use player::PlayerPlugin;
+use powerup::{PowerUpPlugin, PowerUpToSpawn};
+use replay::{Replay, ReplayMode, ReplayPlugin};
+use rng::{rng_start_run_system, GameRng};
+use level::{CurrentLevel, LevelPlugin};
+use state::{despawn_with, AutoStart, GameState, GameStatePlugin};
+use ufo::UfoPlugin;
+use std::collections::{HashMap, HashSet};
+use bevy::ecs::schedule::ExecutorKind;
+use bevy::render::camera::ScalingMode;
+use bevy::transform::TransformSystem;
+use bevy::window::{PrimaryWindow, WindowResized};
+use crate::components::PlayerInvincible;
+
+pub mod bunker;
+pub mod cli;
+pub mod collision;
+pub mod components;
+pub mod enemy;
+pub mod headless;
+pub mod level;
+pub mod player;
+pub mod powerup;
+pub mod replay;
+pub mod rng;
+pub mod state;
+pub mod ufo;
+
+// region:      --- Asset Constants ---
+
+const TIME_STEP: f32 = 1. / 60.;
+const BASE_SPEED: f32 = 500.;
+const DESPAWN_MARGIN: f32 = 200.; // distancia fuera del área a la que se despawnean las entidades
+const COLLISION_CELL_SIZE: f32 = 120.; // lado de las celdas de la rejilla de colisiones
+const PLAYFIELD_SIZE: (f32, f32) = (1920., 1080.);
+
+const PLAYER_SHOOT_SOUND: &str = "player_shoot.ogg";
+const PLAYER_EXPLOSION_SOUND : &str = "player_explosion.ogg";
+const UFO_SOUND: &str = "ufo_loop.wav"; // bucle sin cortes, necesita la feature "wav" de bevy
+
+const PLAYER_SPRITE: &str = "player_a_01.png";
+const PLAYER_SIZE: (f32, f32) = (144., 144.);
+// casco convexo de la nave en píxeles del sprite, de la punta a las alas y la cola
+const PLAYER_HITBOX: [(f32, f32); 9] = [
+    (0., 57.),
+    (-62., -9.),
+    (-62., -21.),
+    (-54., -30.),
+    (-8., -51.),
+    (8., -51.),
+    (54., -30.),
+    (62., -21.),
+    (62., -9.),
+];
+const PLAYER_LASER_SPRITE: &str = "player_laser_a_01.png";
+const PLAYER_LASER_SIZE: (f32, f32) = (9., 54.);
+const PLAYER_LASER_HITBOX: Shape = Shape::Capsule {
+    a: (0., -22.5),
+    b: (0., 22.5),
+    radius: 4.5,
+};
+const PLAYER_LASER_DAMAGE: u32 = 1;
+
+const ENEMY_SPRITE: &str = "enemy_a_01.png";
+const ENEMY_SIZE: (f32, f32) = (93., 84.);
+// casco del sprite de enemigo por defecto, los niveles pueden definir otros
+const ENEMY_HITBOX: [(f32, f32); 6] = [
+    (-30., 40.),
+    (30., 40.),
+    (47., 14.),
+    (26., -42.),
+    (-26., -42.),
+    (-47., 14.),
+];
+const ENEMY_LASER_SPRITE: &str = "enemy_laser_a_01.png";
+const ENEMY_LASER_SIZE: (f32, f32) = (17., 55.);
+// cabeza ancha y cola fina (la cabeza queda abajo al girar el sprite)
+const ENEMY_LASER_HITBOX: [(f32, f32); 6] = [
+    (-2., -27.5),
+    (2., -27.5),
+    (8.5, 21.),
+    (5., 27.5),
+    (-5., 27.5),
+    (-8.5, 21.),
+];
+
+const EXPLOSION_SHEET: &str = "explo_a_sheet.png";
+const EXPLOSION_LEN: usize = 16;
+
+const SPRITE_SCALE: f32 = 0.5;
+
+const DAMAGE_FLASH_TIME: f32 = 0.1;
+const DAMAGE_FLASH_COLOR: Color = Color::rgb(1., 0.2, 0.2);
+
+const PLAYER_RESPAWN_DELAY: f64 = 2.;
+const PLAYER_INVINCIBLE_TIME: f32 = 1.5;
+const PLAYER_HEALTH: u32 = 2; // el escudo aguanta un impacto antes de perder una vida
+const PLAYER_LIVES: u32 = 3;
+const PLAYER_MAX_LIVES: u32 = 5;
+const PLAYER_EXTRA_LIFE_SCORE: usize = 20;
+const PLAYER_SPEED_BOOST: f32 = 1.5; // multiplicador de velocidad del power-up
+const PLAYER_SPEED_BOOST_TIME: f32 = 8.;
+const SCOREBOARD_FONT_SIZE: f32 = 40.;
+const SCOREBOARD_TEXT_PADDING: Val = Val::Px(5.0);
+const SCORE_COLOR: Color = Color::rgb(1.0, 0.5, 0.5);
+const TEXT_COLOR: Color = Color::rgb(0.5, 0.5, 0.5);
+const BOSS_BAR_COLOR: Color = Color::rgb(0.9, 0.2, 0.3);
+const BOSS_BAR_BACKGROUND: Color = Color::rgba(0.2, 0.2, 0.2, 0.8);
+const BACKGROUND_IMAGE : &str = "background.png";
+
+// endregion:   --- Game Constants ---
+
+// region:     --- Resources ---
+
+#[derive(Resource)]
+pub struct ExplosionSound(pub Handle<AudioSource>);
+
+#[derive(Resource)]
+pub struct PlayerShootSound(pub Handle<AudioSource>);
+
+/// Resource - Sonido en bucle del platillo
+#[derive(Resource)]
+pub struct UfoSound(pub Handle<AudioSource>);
+
+#[derive(Resource)]
+pub struct Scoreboard {
+    pub score: usize,
+}
+
+/// Resource - Área de juego en unidades del mundo
+///
+/// `rect` es fijo y es el que usan los spawns, despawns y formaciones; la cámara siempre
+/// lo muestra entero. `visible` es lo que cubre la ventana, que según su proporción puede
+/// ser algo más, y se actualiza con `WindowResized`.
+#[derive(Resource)]
+pub struct Playfield {
+    pub rect: Rect,
+    pub visible: Rect,
+}
+
+impl Default for Playfield {
+    fn default() -> Self {
+        let rect = Rect::from_center_size(Vec2::ZERO, Vec2::from(PLAYFIELD_SIZE));
+        Self { rect, visible: rect }
+    }
+}
+
+impl Playfield {
+    /// recalcular el área visible para una ventana de `width` x `height` píxeles
+    pub fn resize(&mut self, width: f32, height: f32) {
+        // ventana minimizada
+        if width <= 0. || height <= 0. {
+            return;
+        }
+
+        // misma regla que `ScalingMode::AutoMin`: el área de juego cabe entera
+        let scale = (self.rect.width() / width).max(self.rect.height() / height);
+        self.visible = Rect::from_center_size(self.rect.center(), Vec2::new(width, height) * scale);
+    }
+}
+
+/// Resource - Comportamiento del jugador en los bordes del área de juego
+#[derive(Resource)]
+pub struct PlayerBoundary(pub Boundary);
+
+impl Default for PlayerBoundary {
+    fn default() -> Self {
+        Self(Boundary::Clamp)
+    }
+}
+
+#[derive(Resource)]
+pub struct GameTextures {
+    pub player: Handle<Image>,
+    pub player_laser: Handle<Image>,
+    pub enemy: Handle<Image>,
+    pub enemy_laser: Handle<Image>,
+    pub explosion: Handle<TextureAtlas>,
+    pub enemies: HashMap<String, Handle<Image>>, // sprites de los tipos de enemigo
+}
+
+#[derive(Resource)]
+pub struct EnemyCount(pub u32);
+
+/// Resource - Vidas al empezar cada partida (`--lives`, o insertado por quien use el juego)
+#[derive(Resource, Clone, Copy)]
+pub struct StartingLives(pub u32);
+
+impl Default for StartingLives {
+    fn default() -> Self {
+        Self(PLAYER_LIVES)
+    }
+}
+
+#[derive(Resource)]
+pub struct PlayerState {
+    pub on: bool,               // jugador activo
+    pub last_shot: f64,         // -1 si no ha disparado
+    pub lives: u32,             // vidas restantes (incluida la nave actual)
+    pub next_extra_life: usize, // puntuación necesaria para la siguiente vida extra
+    pub weapon: WeaponLevel,    // nivel actual del arma
+    pub fire_cooldown: f32,     // segundos hasta poder volver a disparar
+    pub speed_boost: f32,       // segundos restantes del power-up de velocidad
+}
+
+impl Default for PlayerState {
+    fn default() -> Self {
+        Self::new(PLAYER_LIVES)
+    }
+}
+
+impl PlayerState {
+    /// estado al empezar una partida, con entre 1 y `PLAYER_MAX_LIVES` vidas
+    pub fn new(lives: u32) -> Self {
+        Self {
+            on: false,
+            last_shot: -1.,
+            lives: lives.clamp(1, PLAYER_MAX_LIVES),
+            next_extra_life: PLAYER_EXTRA_LIFE_SCORE,
+            weapon: WeaponLevel::default(),
+            fire_cooldown: 0.,
+            speed_boost: 0.,
+        }
+    }
+
+    pub fn shot(&mut self, time: f64) {
+        self.on = false;
+        self.last_shot = time;
+        self.lives = self.lives.saturating_sub(1);
+        // perder la nave baja un nivel el arma
+        self.weapon = self.weapon.downgrade();
+        self.speed_boost = 0.;
+    }
+
+    pub fn spawned(&mut self) {
+        self.on = true;
+        self.last_shot = -1.;
+    }
+}
+
+/// Nivel del arma del jugador, de menor a mayor
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WeaponLevel {
+    Single,
+    #[default]
+    Twin,
+    TripleSpread,
+    WideSpread,
+    PiercingBeam,
+}
+
+impl WeaponLevel {
+    pub fn upgrade(self) -> Self {
+        match self {
+            WeaponLevel::Single => WeaponLevel::Twin,
+            WeaponLevel::Twin => WeaponLevel::TripleSpread,
+            WeaponLevel::TripleSpread => WeaponLevel::WideSpread,
+            WeaponLevel::WideSpread | WeaponLevel::PiercingBeam => WeaponLevel::PiercingBeam,
+        }
+    }
+
+    pub fn downgrade(self) -> Self {
+        match self {
+            WeaponLevel::Single | WeaponLevel::Twin => WeaponLevel::Single,
+            WeaponLevel::TripleSpread => WeaponLevel::Twin,
+            WeaponLevel::WideSpread => WeaponLevel::TripleSpread,
+            WeaponLevel::PiercingBeam => WeaponLevel::WideSpread,
+        }
+    }
+
+    /// segundos mínimos entre dos disparos (también la cadencia del autodisparo)
+    pub fn fire_cooldown(self) -> f32 {
+        match self {
+            WeaponLevel::Single => 0.25,
+            WeaponLevel::Twin => 0.3,
+            WeaponLevel::TripleSpread => 0.35,
+            WeaponLevel::WideSpread => 0.4,
+            WeaponLevel::PiercingBeam => 0.5,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct PlayerInput {
+    pub left: bool,
+    pub right: bool,
+    pub up: bool,
+    pub down: bool,
+    pub fire: bool, // se mantiene activo hasta que un tick de simulación lo consume
+    pub fire_held: bool, // botón de disparo mantenido (autodisparo)
+}
+
+impl PlayerInput {
+    const LEFT: u8 = 1 << 0;
+    const RIGHT: u8 = 1 << 1;
+    const UP: u8 = 1 << 2;
+    const DOWN: u8 = 1 << 3;
+    const FIRE: u8 = 1 << 4;
+    const FIRE_HELD: u8 = 1 << 5;
+
+    /// empaquetar la entrada en un byte (usado por los replays)
+    pub fn to_bits(&self) -> u8 {
+        let mut bits = 0;
+        for (pressed, bit) in [
+            (self.left, Self::LEFT),
+            (self.right, Self::RIGHT),
+            (self.up, Self::UP),
+            (self.down, Self::DOWN),
+            (self.fire, Self::FIRE),
+            (self.fire_held, Self::FIRE_HELD),
+        ] {
+            if pressed {
+                bits |= bit;
+            }
+        }
+        bits
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            left: bits & Self::LEFT != 0,
+            right: bits & Self::RIGHT != 0,
+            up: bits & Self::UP != 0,
+            down: bits & Self::DOWN != 0,
+            fire: bits & Self::FIRE != 0,
+            fire_held: bits & Self::FIRE_HELD != 0,
+        }
+    }
+}
+
+/// Resource - Reloj de la simulación, avanza TIME_STEP por cada tick de FixedUpdate
+#[derive(Resource, Default)]
+pub struct SimulationTime {
+    pub tick: u64,
+}
+
+impl SimulationTime {
+    pub fn elapsed_seconds_f64(&self) -> f64 {
+        self.tick as f64 * TIME_STEP as f64
+    }
+}
+// endregion:   --- Resources ---
+
+// region:      --- Simulation Sets ---
+
+/// SystemSet - Fases de cada tick de simulación en FixedUpdate
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    Prepare,    // restaurar posiciones y avanzar el reloj
+    Logic,      // spawn, control y disparos
+    Movement,   // mover entidades
+    Detection,  // rejilla de colisiones y eventos de colisión
+    Obstacles,  // lasers detenidos por obstáculos (búnkeres)
+    Collision,  // resolver colisiones
+    Finish,     // guardar posiciones para la interpolación
+}
+
+/// SystemSet - Orden de los plugins dentro de las fases Logic y Collision
+///
+/// Con un solo hilo los sistemas se ejecutan en orden topológico, pero el desempate
+/// entre sistemas ambiguos sale de tablas hash con semilla aleatoria por proceso: sin
+/// estas cadenas, el mismo replay con la misma semilla terminaba con puntuaciones
+/// distintas en ejecuciones distintas.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LogicSet {
+    Player,
+    Enemy,
+    Ufo,
+    PowerUp,
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CollisionSet {
+    Lasers,
+    Ufo,
+    PowerUp,
+}
+
+// endregion:   --- Simulation Sets ---
+
+// endregion:   --- Asset Constants ---
+
+#[wasm_bindgen]
+pub fn run() {
+    let cli = CliArgs::parse();
+    let (game_rng, replay_mode) = game_config(&cli).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    });
+
+    if cli.headless {
+        let mut app = headless::headless_app(game_rng, replay_mode);
+        if let Some(lives) = cli.lives {
+            app.insert_resource(StartingLives(lives));
+        }
+        let summary = headless::run_ticks(&mut app, cli.ticks);
+        println!("{summary}");
+        return;
+    }
+
+    // en modo reproducción se salta el menú
+    let auto_start = matches!(replay_mode, ReplayMode::Playback { .. });
+
+    App::new()
+        .insert_resource(ClearColor(Color::rgb(0.04, 0.04, 0.04)))
+        .insert_resource(game_rng)
+        .insert_resource(replay_mode)
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Spade Invaders!".into(),
+                resizable: true,
+                fit_canvas_to_parent: true,
+                ..default()
+            }),
+            ..default()
+        }))
+        .add_plugins(SpaceInvadersPlugins)
+        .insert_resource(AutoStart(auto_start))
+        .insert_resource(cli.lives.map_or_else(StartingLives::default, StartingLives))
+        .run();
+}
+
+/// crear el generador aleatorio y el modo de replay a partir de la línea de comandos
+///
+/// Si se pide un replay que no se puede cargar es un error: jugar o grabar otra
+/// partida en su lugar confundiría a quien intenta reproducirla.
+fn game_config(cli: &CliArgs) -> Result<(GameRng, ReplayMode), String> {
+    // reproducir un replay tiene prioridad sobre la grabación
+    if let Some(path) = cli.replay.as_ref() {
+        let replay = Replay::load(path)
+            .map_err(|err| format!("no se pudo cargar el replay {}: {err}", path.display()))?;
+        let game_rng = GameRng::new(Some(replay.seed));
+        return Ok((game_rng, ReplayMode::Playback { replay, cursor: 0 }));
+    }
+
+    let replay_mode = match cli.record.clone() {
+        Some(path) => ReplayMode::Record {
+            path,
+            replay: Replay::default(),
+        },
+        None => ReplayMode::Off,
+    };
+
+    Ok((GameRng::new(cli.seed), replay_mode))
+}
+
+/// PluginGroup - Todos los plugins del juego
+///
+/// Para usar el juego sin ventana se desactiva `WindowedPlugin` y se añade
+/// `headless::HeadlessPlugin`.
+pub struct SpaceInvadersPlugins;
+
+impl PluginGroup for SpaceInvadersPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(GamePlugin)
+            .add(GameStatePlugin)
+            .add(LevelPlugin)
+            .add(ReplayPlugin)
+            .add(PlayerPlugin)
+            .add(EnemyPlugin)
+            .add(PowerUpPlugin)
+            .add(BunkerPlugin)
+            .add(UfoPlugin)
+            .add(WindowedPlugin)
+    }
+}
+
+/// Plugin - Lógica del juego, común al modo con ventana y al modo headless
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Scoreboard { score: 0 })
+            .insert_resource(EnemyCount(0))
+            .init_resource::<GameRng>()
+            .insert_resource(FixedTime::new_from_secs(TIME_STEP))
+            .init_resource::<PlayerInput>()
+            .init_resource::<Playfield>()
+            .init_resource::<PlayerBoundary>()
+            .insert_resource(CollisionGrid::new(
+                Playfield::default().rect.inset(DESPAWN_MARGIN),
+                COLLISION_CELL_SIZE,
+            ))
+            .init_resource::<SimulationTime>()
+            .add_event::<CollisionEvent>()
+            // ejecutar la simulación en un solo hilo; los sistemas que usan el generador
+            // aleatorio o crean entidades se encadenan para que su orden sea siempre el mismo
+            .edit_schedule(FixedUpdate, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+            })
+            .configure_sets(
+                FixedUpdate,
+                (
+                    SimulationSet::Prepare.run_if(in_state(GameState::Playing)),
+                    SimulationSet::Logic.run_if(in_state(GameState::Playing)),
+                    SimulationSet::Movement.run_if(in_state(GameState::Playing)),
+                    SimulationSet::Detection.run_if(in_state(GameState::Playing)),
+                    SimulationSet::Obstacles.run_if(in_state(GameState::Playing)),
+                    SimulationSet::Collision.run_if(in_state(GameState::Playing)),
+                    SimulationSet::Finish.run_if(in_state(GameState::Playing)),
+                )
+                    .chain(),
+            )
+            .configure_sets(
+                FixedUpdate,
+                (LogicSet::Player, LogicSet::Enemy, LogicSet::Ufo, LogicSet::PowerUp)
+                    .chain()
+                    .in_set(SimulationSet::Logic),
+            )
+            .configure_sets(
+                FixedUpdate,
+                (CollisionSet::Lasers, CollisionSet::Ufo, CollisionSet::PowerUp)
+                    .chain()
+                    .in_set(SimulationSet::Collision),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    (simulation_tick_system, interpolation_restore_system)
+                        .chain()
+                        .in_set(SimulationSet::Prepare),
+                    movable_system.in_set(SimulationSet::Movement),
+                    (collision_grid_system, collision_system)
+                        .chain()
+                        .in_set(SimulationSet::Detection),
+                    (player_laser_hit_enemy_system, enemy_hit_player_system)
+                        .chain()
+                        .in_set(CollisionSet::Lasers),
+                    interpolation_capture_system.in_set(SimulationSet::Finish),
+                ),
+            )
+            .add_systems(
+                Update,
+                (explosion_to_spawn_system, explosion_animation_system, damage_flash_system)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                PostUpdate,
+                interpolation_render_system.before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(OnExit(GameState::Menu), rng_start_run_system)
+            .add_systems(
+                OnEnter(GameState::Menu),
+                (
+                    despawn_with::<Laser>,
+                    despawn_with::<Explosion>,
+                    despawn_with::<ExplosionToSpawn>,
+                    reset_scoreboard_system,
+                    reset_simulation_system,
+                ),
+            );
+    }
+}
+
+/// Plugin - Assets, cámara y HUD del modo con ventana
+pub struct WindowedPlugin;
+
+impl Plugin for WindowedPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_system)
+            .add_systems(OnExit(GameState::Loading), enemy_textures_system)
+            .add_systems(
+                Update,
+                (
+                    update_scoreboard_system,
+                    update_lives_system,
+                    update_boss_health_bar_system,
+                    playfield_resize_system,
+                    background_resize_system.run_if(resource_changed::<Playfield>()),
+                    bevy::window::close_on_esc,
+                ),
+            );
+    }
+}
+
+fn setup_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut playfield: ResMut<Playfield>,
+    query: Query<&Window, With<PrimaryWindow>>,
+) {
+
+    // insertar sonido de disparo para el jugador
+    let player_shoot_sound = asset_server.load(PLAYER_SHOOT_SOUND);
+    commands.insert_resource(PlayerShootSound(player_shoot_sound));
+
+    // insertar sonido de explosión
+    let explosion_sound = asset_server.load(PLAYER_EXPLOSION_SOUND);
+    commands.insert_resource(ExplosionSound(explosion_sound));
+
+    // insertar sonido del platillo volante
+    let ufo_sound = asset_server.load(UFO_SOUND);
+    commands.insert_resource(UfoSound(ufo_sound));
+
+    // camara del juego
+    let mut camera = Camera2dBundle::default();
+
+    // mostrar siempre el área de juego entera, sin deformarla, sea cual sea la ventana
+    camera.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: PLAYFIELD_SIZE.0,
+        min_height: PLAYFIELD_SIZE.1,
+    };
+
+    // insertar la camara
+    commands.spawn(camera);
+
+    // insertar fondo
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load(BACKGROUND_IMAGE),
+            ..default()
+        },
+        Background,
+    ));
+
+    // Scoreboard
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "Score: ",
+                TextStyle {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ),
+            TextSection::from_style(TextStyle {
+                font_size: SCOREBOARD_FONT_SIZE,
+                color: SCORE_COLOR,
+                ..default()
+            }),
+        ])
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: SCOREBOARD_TEXT_PADDING,
+                left: SCOREBOARD_TEXT_PADDING,
+                ..default()
+            }),
+        ScoreText,
+    ));
+
+    // Vidas
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "Lives: ",
+                TextStyle {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ),
+            TextSection::from_style(TextStyle {
+                font_size: SCOREBOARD_FONT_SIZE,
+                color: SCORE_COLOR,
+                ..default()
+            }),
+        ])
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: SCOREBOARD_TEXT_PADDING,
+                right: SCOREBOARD_TEXT_PADDING,
+                ..default()
+            }),
+        LivesText,
+    ));
+
+    // barra de vida del jefe, oculta hasta que aparece uno
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(SCOREBOARD_FONT_SIZE + 20.),
+                    left: Val::Percent(25.),
+                    width: Val::Percent(50.),
+                    height: Val::Px(16.),
+                    ..default()
+                },
+                background_color: BOSS_BAR_BACKGROUND.into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            BossHealthBar,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    background_color: BOSS_BAR_COLOR.into(),
+                    ..default()
+                },
+                BossHealthFill,
+            ));
+        });
+
+    // área visible inicial; después la actualiza `playfield_resize_system`
+    if let Ok(primary) = query.get_single() {
+        playfield.resize(primary.width(), primary.height());
+    }
+
+    // añadir recursos de explosiones
+    let texture_handle = asset_server.load(EXPLOSION_SHEET);
+    let texture_atlas = TextureAtlas::from_grid(texture_handle, Vec2::new(64., 64.), 4, 4, None, None);
+    let explosion = texture_atlases.add(texture_atlas);
+
+    // añadir recursos de texturas
+    let game_textures = GameTextures {
+        player: asset_server.load(PLAYER_SPRITE),
+        player_laser: asset_server.load(PLAYER_LASER_SPRITE),
+        enemy: asset_server.load(ENEMY_SPRITE),
+        enemy_laser: asset_server.load(ENEMY_LASER_SPRITE),
+        explosion,
+        enemies: HashMap::new(),
+    };
+    commands.insert_resource(game_textures);
+}
+
+// cargar los sprites de los tipos de enemigo del nivel
+fn enemy_textures_system(
+    asset_server: Res<AssetServer>,
+    current_level: Res<CurrentLevel>,
+    mut game_textures: ResMut<GameTextures>,
+) {
+    for archetype in current_level.0.archetypes.values() {
+        game_textures
+            .enemies
+            .entry(archetype.sprite.clone())
+            .or_insert_with(|| asset_server.load(&archetype.sprite));
+    }
+}
+
+// avanzar el reloj de la simulación
+fn simulation_tick_system(mut sim_time: ResMut<SimulationTime>) {
+    sim_time.tick += 1;
+}
+
+// devolver las entidades a su posición simulada antes de avanzar el tick
+fn interpolation_restore_system(mut query: Query<(&mut Transform, &mut Interpolated)>) {
+    for (mut transform, mut interpolated) in query.iter_mut() {
+        transform.translation = interpolated.current;
+        interpolated.previous = interpolated.current;
+    }
+}
+
+// guardar la posición simulada al final del tick
+fn interpolation_capture_system(mut query: Query<(&Transform, &mut Interpolated)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.current = transform.translation;
+    }
+}
+
+// interpolar entre los dos últimos ticks según el tiempo acumulado
+fn interpolation_render_system(
+    fixed_time: Res<FixedTime>,
+    mut query: Query<(&mut Transform, &Interpolated)>,
+) {
+    let alpha = (fixed_time.accumulated().as_secs_f32() / fixed_time.period.as_secs_f32()).min(1.);
+    for (mut transform, interpolated) in query.iter_mut() {
+        transform.translation = interpolated.previous.lerp(interpolated.current, alpha);
+    }
+}
+
+// entidad que se mueve con su velocidad; el tamaño y la interpolación son opcionales
+type MovableItem = (
+    Entity,
+    &'static Velocity,
+    &'static mut Transform,
+    &'static Movable,
+    Option<&'static SpriteSize>,
+    Option<&'static mut Interpolated>,
+);
+
+fn movable_system(
+    mut commands: Commands,
+    playfield: Res<Playfield>,
+    mut query: Query<MovableItem>,
+) {
+    let rect = playfield.rect;
+
+    for (entity, velocity, mut transform, movable, size, interpolated) in query.iter_mut() {
+        transform.translation.x += velocity.x * TIME_STEP * BASE_SPEED;
+        transform.translation.y += velocity.y * TIME_STEP * BASE_SPEED;
+
+        // mitad del tamaño del sprite, para que Clamp y Wrap usen el borde y no el centro
+        let half = size.map_or(Vec2::ZERO, |size| size.0 * transform.scale.xy() / 2.);
+        let translation = &mut transform.translation;
+
+        match movable.boundary {
+            Boundary::Despawn => {
+                if !rect.inset(DESPAWN_MARGIN).contains(translation.xy()) {
+                    commands.entity(entity).despawn();
+                }
+            }
+            Boundary::Clamp => {
+                translation.x = translation.x.clamp(rect.min.x + half.x, rect.max.x - half.x);
+                translation.y = translation.y.clamp(rect.min.y + half.y, rect.max.y - half.y);
+            }
+            Boundary::Wrap => {
+                let mut wrapped = true;
+                if translation.x > rect.max.x + half.x {
+                    translation.x = rect.min.x - half.x;
+                } else if translation.x < rect.min.x - half.x {
+                    translation.x = rect.max.x + half.x;
+                } else if translation.y > rect.max.y + half.y {
+                    translation.y = rect.min.y - half.y;
+                } else if translation.y < rect.min.y - half.y {
+                    translation.y = rect.max.y + half.y;
+                } else {
+                    wrapped = false;
+                }
+
+                // no interpolar el salto de un borde al otro
+                if let (true, Some(mut interpolated)) = (wrapped, interpolated) {
+                    interpolated.previous = *translation;
+                }
+            }
+            Boundary::Free => {}
+        }
+    }
+}
+
+// lasers disparados por el jugador
+type PlayerLaser = (With<Laser>, With<FromPlayer>);
+
+fn player_laser_hit_enemy_system(
+    mut commands: Commands,
+    mut enemy_count: ResMut<EnemyCount>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut events: EventReader<CollisionEvent>,
+    mut laser_query: Query<(&Damage, Option<&mut Piercing>), PlayerLaser>,
+    mut enemy_query: Query<
+        (&Transform, &mut Health, &EnemyStats, &mut DamageFlash),
+        With<Enemy>,
+    >,
+    mut despawned_entities: Local<HashSet<Entity>>,
+) {
+    despawned_entities.clear();
+
+    for event in events.iter() {
+        if !event.is(layer::ENEMY, layer::PLAYER_LASER) {
+            continue;
+        }
+
+        let (enemy_entity, laser_entity) = (event.entity, event.other);
+        if despawned_entities.contains(&enemy_entity) || despawned_entities.contains(&laser_entity) {
+            continue;
+        }
+
+        let (Ok((enemy_tf, mut health, stats, mut flash)), Ok((damage, mut piercing))) =
+            (enemy_query.get_mut(enemy_entity), laser_query.get_mut(laser_entity))
+        else {
+            continue;
+        };
+
+        // un laser perforante solo daña una vez a cada enemigo
+        match piercing.as_mut() {
+            Some(piercing) if piercing.hits.contains(&enemy_entity) => continue,
+            Some(piercing) => piercing.hits.push(enemy_entity),
+            None => {
+                // remover el laser
+                commands.entity(laser_entity).despawn();
+                despawned_entities.insert(laser_entity);
+            }
+        }
+
+        // el enemigo resiste mientras le queden puntos de vida
+        if !health.damage(damage.0) {
+            flash.time_left = DAMAGE_FLASH_TIME;
+            continue;
+        }
+
+        // remover el enemigo
+        commands.entity(enemy_entity).despawn();
+        despawned_entities.insert(enemy_entity);
+        enemy_count.0 -= 1;
+
+        // iniciar la animacion de explosion
+        commands
+            .spawn(ExplosionToSpawn(enemy_tf.translation.clone()));
+
+        // el enemigo puede soltar un power-up
+        commands.spawn(PowerUpToSpawn(enemy_tf.translation));
+
+        // aumentar la puntuación según el tipo de enemigo
+        scoreboard.score += stats.score as usize;
+    }
+}
+
+// lasers enemigos y enemigos que embisten al jugador
+fn enemy_hit_player_system(
+    mut commands: Commands,
+    mut player_state: ResMut<PlayerState>,
+    mut player_invincible_query: Query<(Entity, &mut PlayerInvincible)>,
+    sim_time: Res<SimulationTime>,
+    mut events: EventReader<CollisionEvent>,
+    laser_query: Query<&Damage, (With<Laser>, With<FromEnemy>)>,
+    mut player_query: Query<(&Transform, &mut Health, &mut DamageFlash), With<Player>>,
+) {
+
+    for(player_entity, mut player_invincible) in player_invincible_query.iter_mut() {
+        player_invincible.time_left -= TIME_STEP;
+
+        if player_invincible.time_left <= 0. {
+            commands.entity(player_entity).remove::<PlayerInvincible>();
+        }
+    }
+
+    for event in events.iter() {
+        if !event.is(layer::PLAYER, layer::ENEMY_LASER | layer::ENEMY) {
+            continue;
+        }
+
+        // el jugador ya ha sido destruido en este tick
+        if !player_state.on {
+            continue;
+        }
+
+        let player_entity = event.entity;
+        let Ok((player_tf, mut health, mut flash)) = player_query.get_mut(player_entity) else {
+            continue;
+        };
+        let invincible = player_invincible_query.get(player_entity).is_ok();
+
+        if event.other_layer & layer::ENEMY_LASER != 0 {
+            let Ok(damage) = laser_query.get(event.other) else {
+                continue;
+            };
+
+            // remover el laser, también si el jugador es invencible
+            commands.entity(event.other).despawn();
+            if invincible {
+                continue;
+            }
+
+            // el escudo absorbe el impacto mientras le quede vida
+            if !health.damage(damage.0) {
+                flash.time_left = DAMAGE_FLASH_TIME;
+                continue;
+            }
+        } else if invincible {
+            continue;
+        }
+
+        // remover el jugador; chocar con un enemigo lo destruye aunque tenga escudo
+        commands.entity(player_entity).despawn();
+        player_state.shot(sim_time.elapsed_seconds_f64());
+
+        // iniciar la animacion de explosion
+        commands
+            .spawn(ExplosionToSpawn(player_tf.translation.clone()));
+    }
+}
+
+fn damage_flash_system(time: Res<Time>, mut query: Query<(&mut Sprite, &mut DamageFlash)>) {
+    for (mut sprite, mut flash) in query.iter_mut() {
+        if flash.time_left <= 0. {
+            continue;
+        }
+
+        // guardar el color original al empezar el destello
+        if flash.color.is_none() {
+            flash.color = Some(sprite.color);
+            sprite.color = DAMAGE_FLASH_COLOR;
+        }
+
+        flash.time_left -= time.delta_seconds();
+        if flash.time_left <= 0. {
+            sprite.color = flash.color.take().unwrap_or(Color::WHITE);
+        }
+    }
+}
+
+fn explosion_to_spawn_system(
+    mut commands: Commands,
+    game_texture: Res<GameTextures>,
+    query: Query<(Entity, &ExplosionToSpawn)>,
+    sound: Option<Res<ExplosionSound>>, // no existe en modo headless
+) {
+    for (explosion_spawn_entity, explosion_to_spawn) in query.iter() {
+        // crear la entidad de explosion
+        commands
+            .spawn(SpriteSheetBundle {
+                texture_atlas: game_texture.explosion.clone(),
+                transform: Transform {
+                    translation: explosion_to_spawn.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(Explosion)
+            .insert(ExplosionTimer::default());
+
+        if let Some(sound) = sound.as_ref() {
+            commands.spawn(AudioBundle {
+                source: sound.0.clone(),
+                // auto-despawn al terminar de reproducir el sonido
+                settings: PlaybackSettings::DESPAWN,
+            });
+        }
+
+        // despawnear la entidad de explosion_to_spawn
+        commands.entity(explosion_spawn_entity).despawn();
+    }
+}
+
+fn explosion_animation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ExplosionTimer, &mut TextureAtlasSprite), With<Explosion>>,
+) {
+    for (entity, mut timer, mut sprite) in query.iter_mut() {
+        timer.0.tick(time.delta());
+        if timer.0.finished() {
+            sprite.index += 1; // mover al siguiente frame
+            if sprite.index >= EXPLOSION_LEN {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+// mantener el área visible al día con el tamaño de la ventana
+fn playfield_resize_system(
+    mut events: EventReader<WindowResized>,
+    mut playfield: ResMut<Playfield>,
+) {
+    for event in events.iter() {
+        playfield.resize(event.width, event.height);
+    }
+}
+
+// escalar el fondo para que cubra toda el área visible
+fn background_resize_system(
+    playfield: Res<Playfield>,
+    mut query: Query<&mut Transform, With<Background>>,
+) {
+    let scale = (playfield.visible.size() / playfield.rect.size()).max_element();
+    for mut transform in query.iter_mut() {
+        transform.scale = Vec3::new(scale, scale, 1.);
+    }
+}
+
+// sistema de puntuación
+fn update_scoreboard_system(
+    scoreboard: Res<Scoreboard>,
+    mut query: Query<&mut Text, With<ScoreText>>,
+) {
+    let mut text = query.single_mut();
+    text.sections[1].value = scoreboard.score.to_string();
+}
+
+// barra de vida del jefe
+fn update_boss_health_bar_system(
+    boss_query: Query<&Boss>,
+    mut bar_query: Query<&mut Visibility, With<BossHealthBar>>,
+    mut fill_query: Query<&mut Style, With<BossHealthFill>>,
+) {
+    // mostrar la barra solo mientras haya un jefe
+    let boss = boss_query.iter().next();
+    for mut visibility in bar_query.iter_mut() {
+        *visibility = if boss.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    if let Some(boss) = boss {
+        for mut style in fill_query.iter_mut() {
+            style.width = Val::Percent(boss.health_fraction() * 100.);
+        }
+    }
+}
+
+// sistema de vidas
+fn update_lives_system(
+    player_state: Res<PlayerState>,
+    mut query: Query<&mut Text, With<LivesText>>,
+) {
+    let mut text = query.single_mut();
+    text.sections[1].value = player_state.lives.to_string();
+}
+
+// reiniciar la puntuación al volver al menú
+fn reset_scoreboard_system(mut scoreboard: ResMut<Scoreboard>) {
+    scoreboard.score = 0;
+}
+
+// reiniciar el reloj y la entrada de la simulación al volver al menú
+fn reset_simulation_system(
+    mut sim_time: ResMut<SimulationTime>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    *sim_time = SimulationTime::default();
+    *player_input = PlayerInput::default();
+}
\ No newline at end of file
@@ -1,5 +1,12 @@
-use crate::components::{FromPlayer, Laser, Movable, Player, PlayerInvincible, SpriteSize, Velocity};
-use crate::{GameTextures, PlayerState, WinSize, PLAYER_INVINCIBLE_TIME, PLAYER_LASER_SIZE, PLAYER_RESPAWN_DELAY, PLAYER_SIZE, SPRITE_SCALE, PlayerShootSound};
+use crate::collision::{layer, Collider, Hitbox, Shape};
+use crate::components::{Boundary, Damage, DamageFlash, FromPlayer, Health, Interpolated, Laser, Movable, Piercing, Player, PlayerInvincible, SpriteSize, Velocity};
+use crate::state::{despawn_with, GameState};
+use crate::{GameTextures, PlayerBoundary, PlayerInput, PlayerState, Scoreboard, LogicSet, SimulationTime, Playfield, PLAYER_EXTRA_LIFE_SCORE, PLAYER_HEALTH, PLAYER_HITBOX, PLAYER_INVINCIBLE_TIME, PLAYER_LASER_DAMAGE, PLAYER_LASER_HITBOX, PLAYER_LASER_SIZE, PLAYER_MAX_LIVES, PLAYER_RESPAWN_DELAY, PLAYER_SIZE, PLAYER_SPEED_BOOST, SPRITE_SCALE, PlayerShootSound, StartingLives, WeaponLevel, TIME_STEP};
+
+// ángulo entre los lasers de los disparos en abanico
+const PLAYER_SPREAD_ANGLE: f32 = 0.15;
+// longitud del rayo perforante respecto a un laser normal
+const PLAYER_BEAM_LENGTH: f32 = 3.;
 
 use bevy::prelude::*;
 
@@ -7,43 +14,77 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(PlayerState::default())
-            .add_systems(Update, player_spawn_system)
-            .add_systems(Update, player_keyboard_event_system)
-            .add_systems(Update, player_fire_system);
+        app.init_resource::<StartingLives>()
+            .insert_resource(PlayerState::default())
+            .add_systems(
+                Update,
+                player_keyboard_event_system.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    player_spawn_system,
+                    player_extra_life_system,
+                    player_control_system,
+                    player_fire_system,
+                )
+                    .chain()
+                    .in_set(LogicSet::Player),
+            )
+            .add_systems(
+                OnEnter(GameState::Menu),
+                (despawn_with::<Player>, player_reset_system),
+            );
     }
 }
 
+fn player_reset_system(mut player_state: ResMut<PlayerState>, starting_lives: Res<StartingLives>) {
+    *player_state = PlayerState::new(starting_lives.0);
+}
+
 fn player_spawn_system(
     mut commands: Commands,
     mut player_state: ResMut<PlayerState>,
-    time: Res<Time>,
+    mut next_state: ResMut<NextState<GameState>>,
+    sim_time: Res<SimulationTime>,
     game_textures: Res<GameTextures>,
-    win_size: Res<WinSize>,
+    playfield: Res<Playfield>,
+    player_boundary: Res<PlayerBoundary>,
 ) {
-    let now = time.elapsed_seconds_f64();
+    let now = sim_time.elapsed_seconds_f64();
     let last_shot = player_state.last_shot;
 
     if !player_state.on && (last_shot == -1. || now > last_shot + PLAYER_RESPAWN_DELAY) {
-        let bottom = -win_size.h / 2.;
+        // sin vidas restantes, terminar la partida en lugar de reaparecer
+        if player_state.lives == 0 {
+            next_state.set(GameState::GameOver);
+            return;
+        }
+
+        let bottom = playfield.rect.min.y;
+        let translation = Vec3::new(0., bottom + PLAYER_SIZE.1 / 2. * SPRITE_SCALE + 5., 10.);
         commands
             .spawn(SpriteBundle {
                 texture: game_textures.player.clone(),
                 transform: Transform {
-                    translation: Vec3::new(
-                        0.,
-                        bottom + PLAYER_SIZE.1 / 2. * SPRITE_SCALE + 5.,
-                        10.,
-                    ),
+                    translation,
                     scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
                     ..Default::default()
                 },
                 ..Default::default()
             })
             .insert(Player)
+            .insert(Interpolated::from(translation))
             .insert(SpriteSize::from(PLAYER_SIZE))
+            .insert(Hitbox(Shape::Polygon(PLAYER_HITBOX.to_vec())))
+            .insert(Collider::new(
+                layer::PLAYER,
+                layer::ENEMY_LASER | layer::ENEMY | layer::POWER_UP,
+            ))
+            .insert(Health::new(PLAYER_HEALTH))
+            .insert(DamageFlash::default())
             .insert(Movable {
-                auto_despawn: false,
+                boundary: player_boundary.0,
             })
             .insert(Velocity { x: 0., y: 0. })
             .insert(PlayerInvincible {
@@ -55,62 +96,137 @@ fn player_spawn_system(
     }
 }
 
+fn player_extra_life_system(
+    scoreboard: Res<Scoreboard>,
+    mut player_state: ResMut<PlayerState>,
+) {
+    // otorgar una vida extra cada vez que se alcanza el umbral de puntuación
+    while scoreboard.score >= player_state.next_extra_life {
+        player_state.lives = (player_state.lives + 1).min(PLAYER_MAX_LIVES);
+        player_state.next_extra_life += PLAYER_EXTRA_LIFE_SCORE;
+    }
+}
+
 fn player_fire_system(
     mut commands: Commands,
-    kb: Res<Input<KeyCode>>,
+    mut player_input: ResMut<PlayerInput>,
+    mut player_state: ResMut<PlayerState>,
     game_textures: Res<GameTextures>,
     query: Query<&Transform, With<Player>>,
-    sound: Res<PlayerShootSound>
+    sound: Option<Res<PlayerShootSound>>, // no existe en modo headless
 ) {
+    // consumir el disparo pendiente aunque el jugador no esté activo
+    let fire = std::mem::take(&mut player_input.fire);
+
+    player_state.fire_cooldown = (player_state.fire_cooldown - TIME_STEP).max(0.);
+
     if let Ok(player_tf) = query.get_single() {
-        if kb.just_pressed(KeyCode::Space) {
+        // disparar al pulsar o, manteniendo el botón, cada vez que el arma se recarga
+        if (fire || player_input.fire_held) && player_state.fire_cooldown <= 0. {
+            player_state.fire_cooldown = player_state.weapon.fire_cooldown();
+
             let (x, y) = (player_tf.translation.x, player_tf.translation.y);
             let x_offset = PLAYER_SIZE.0 / 2. * SPRITE_SCALE - 5.;
 
-            let mut spawn_laser = |x_offset: f32| {
-                commands
-                    .spawn(SpriteBundle {
-                        texture: game_textures.player_laser.clone(),
-                        transform: Transform {
-                            translation: Vec3::new(x + x_offset, y + 15., 0.),
-                            scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE, 1.),
-                            ..Default::default()
-                        },
-                        ..Default::default()
-                    })
+            // angle en radianes respecto a la vertical, positivo hacia la derecha
+            let mut spawn_laser = |x_offset: f32, angle: f32, beam: bool| {
+                let translation = Vec3::new(x + x_offset, y + 15., 0.);
+                let length = if beam { PLAYER_BEAM_LENGTH } else { 1. };
+                let mut laser = commands.spawn(SpriteBundle {
+                    texture: game_textures.player_laser.clone(),
+                    transform: Transform {
+                        translation,
+                        rotation: Quat::from_rotation_z(-angle),
+                        scale: Vec3::new(SPRITE_SCALE, SPRITE_SCALE * length, 1.),
+                    },
+                    ..Default::default()
+                });
+                laser
                     .insert(Laser)
                     .insert(FromPlayer)
+                    .insert(Damage(PLAYER_LASER_DAMAGE))
+                    .insert(Interpolated::from(translation))
                     .insert(SpriteSize::from(PLAYER_LASER_SIZE))
-                    .insert(Movable { auto_despawn: true })
-                    .insert(Velocity { x: 0., y: 1. });
+                    .insert(Hitbox(PLAYER_LASER_HITBOX))
+                    .insert(Collider::new(layer::PLAYER_LASER, 0))
+                    .insert(Movable { boundary: Boundary::Despawn })
+                    .insert(Velocity {
+                        x: angle.sin(),
+                        y: angle.cos(),
+                    });
                     /*
                     .insert(AudioBundle {
                         source: sound.0.clone(),
                         settings: PlaybackSettings::DESPAWN,
                     });
                      */
+
+                if beam {
+                    laser.insert(Piercing::default());
+                }
             };
 
-            spawn_laser(x_offset);
-            spawn_laser(-x_offset);
-            commands.spawn(AudioBundle {
-                source: sound.0.clone(),
-                settings: PlaybackSettings::default(),
-            });
+            match player_state.weapon {
+                WeaponLevel::Single => spawn_laser(0., 0., false),
+                WeaponLevel::Twin => {
+                    spawn_laser(x_offset, 0., false);
+                    spawn_laser(-x_offset, 0., false);
+                }
+                WeaponLevel::TripleSpread => {
+                    for angle in [-PLAYER_SPREAD_ANGLE, 0., PLAYER_SPREAD_ANGLE] {
+                        spawn_laser(0., angle, false);
+                    }
+                }
+                WeaponLevel::WideSpread => {
+                    for step in -2..=2 {
+                        spawn_laser(0., step as f32 * PLAYER_SPREAD_ANGLE, false);
+                    }
+                }
+                WeaponLevel::PiercingBeam => spawn_laser(0., 0., true),
+            }
+
+            if let Some(sound) = sound.as_ref() {
+                commands.spawn(AudioBundle {
+                    source: sound.0.clone(),
+                    settings: PlaybackSettings::DESPAWN,
+                });
+            }
         }
     }
 }
 
 fn player_keyboard_event_system(
     kb: Res<Input<KeyCode>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    // registrar las teclas para el siguiente tick de simulación
+    player_input.left = kb.pressed(KeyCode::A);
+    player_input.right = kb.pressed(KeyCode::D);
+    player_input.up = kb.pressed(KeyCode::W);
+    player_input.down = kb.pressed(KeyCode::S);
+    player_input.fire |= kb.just_pressed(KeyCode::Space);
+    player_input.fire_held = kb.pressed(KeyCode::Space);
+}
+
+fn player_control_system(
+    player_input: Res<PlayerInput>,
+    mut player_state: ResMut<PlayerState>,
     mut query: Query<&mut Velocity, With<Player>>,
 ) {
+    // el power-up de velocidad dura un tiempo limitado
+    player_state.speed_boost = (player_state.speed_boost - TIME_STEP).max(0.);
+    let speed = if player_state.speed_boost > 0. {
+        PLAYER_SPEED_BOOST
+    } else {
+        1.
+    };
+
     // funciones para el eje X (izquierda y derecha)
     if let Ok(mut velocity) = query.get_single_mut() {
-        velocity.x = if kb.pressed(KeyCode::A) {
-            -0.8
-        } else if kb.pressed(KeyCode::D) {
-            0.8
+        velocity.x = if player_input.left {
+            -0.8 * speed
+        } else if player_input.right {
+            0.8 * speed
         } else {
             0.
         }
@@ -118,10 +234,10 @@ fn player_keyboard_event_system(
 
     // funciones para el eje Y (arriba y abajo)
     if let Ok(mut velocity) = query.get_single_mut() {
-        velocity.y = if kb.pressed(KeyCode::W) {
-            0.5
-        } else if kb.pressed(KeyCode::S) {
-            -0.5
+        velocity.y = if player_input.up {
+            0.5 * speed
+        } else if player_input.down {
+            -0.5 * speed
         } else {
             0.
         }
@@ -0,0 +1,156 @@
+use crate::collision::{layer, Collider, CollisionEvent};
+use crate::components::{Boundary, FromEnemy, Health, Interpolated, Laser, Movable, Player, SpriteSize, Velocity};
+use crate::rng::GameRng;
+use crate::state::{despawn_with, GameState};
+use crate::{CollisionSet, LogicSet, PlayerState, PLAYER_HEALTH, PLAYER_MAX_LIVES, PLAYER_SPEED_BOOST_TIME};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+// probabilidad de que un enemigo destruido suelte un power-up
+const POWER_UP_DROP_CHANCE: f64 = 0.1;
+const POWER_UP_SIZE: (f32, f32) = (40., 40.);
+// velocidad de caída, relativa a BASE_SPEED
+const POWER_UP_FALL_SPEED: f32 = 0.3;
+
+// tabla de drops: tipo de power-up y su peso
+const POWER_UP_DROP_TABLE: [(PowerUpKind, u32); 5] = [
+    (PowerUpKind::Weapon, 4),
+    (PowerUpKind::Shield, 3),
+    (PowerUpKind::SpeedBoost, 3),
+    (PowerUpKind::Bomb, 2),
+    (PowerUpKind::ExtraLife, 1),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerUpKind {
+    Weapon,     // sube un nivel el arma
+    Shield,     // recarga el escudo de la nave
+    ExtraLife,  // una vida más
+    Bomb,       // destruye todos los lasers enemigos
+    SpeedBoost, // más velocidad durante un tiempo
+}
+
+impl PowerUpKind {
+    fn color(self) -> Color {
+        match self {
+            PowerUpKind::Weapon => Color::rgb(1., 0.6, 0.1),
+            PowerUpKind::Shield => Color::rgb(0.2, 0.6, 1.),
+            PowerUpKind::ExtraLife => Color::rgb(0.3, 1., 0.3),
+            PowerUpKind::Bomb => Color::rgb(1., 0.2, 0.2),
+            PowerUpKind::SpeedBoost => Color::rgb(1., 1., 0.3),
+        }
+    }
+}
+
+/// Component - Power-up que cae hasta que el jugador lo recoge
+#[derive(Component)]
+pub struct PowerUp(pub PowerUpKind);
+
+/// Component - Posición de un enemigo destruido que puede soltar un power-up
+#[derive(Component)]
+pub struct PowerUpToSpawn(pub Vec3);
+
+/// Plugin - Drops de power-ups y sus efectos
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, power_up_drop_system.in_set(LogicSet::PowerUp))
+            .add_systems(
+                FixedUpdate,
+                power_up_collect_system.in_set(CollisionSet::PowerUp),
+            )
+            .add_systems(
+                OnEnter(GameState::Menu),
+                (despawn_with::<PowerUp>, despawn_with::<PowerUpToSpawn>),
+            );
+    }
+}
+
+// elegir un power-up de la tabla según los pesos
+fn roll_power_up(rng: &mut impl Rng) -> PowerUpKind {
+    let total: u32 = POWER_UP_DROP_TABLE.iter().map(|(_, weight)| weight).sum();
+    let mut roll = rng.gen_range(0..total);
+    for (kind, weight) in POWER_UP_DROP_TABLE {
+        if roll < weight {
+            return kind;
+        }
+        roll -= weight;
+    }
+    unreachable!()
+}
+
+fn power_up_drop_system(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    query: Query<(Entity, &PowerUpToSpawn)>,
+) {
+    for (entity, to_spawn) in query.iter() {
+        commands.entity(entity).despawn();
+
+        if !rng.gen_bool(POWER_UP_DROP_CHANCE) {
+            continue;
+        }
+
+        let kind = roll_power_up(rng.as_mut());
+        let translation = Vec3::new(to_spawn.0.x, to_spawn.0.y, 5.);
+        commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: kind.color(),
+                    custom_size: Some(Vec2::from(POWER_UP_SIZE)),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(translation),
+                ..Default::default()
+            })
+            .insert(PowerUp(kind))
+            .insert(Interpolated::from(translation))
+            .insert(SpriteSize::from(POWER_UP_SIZE))
+            .insert(Collider::new(layer::POWER_UP, 0))
+            .insert(Movable { boundary: Boundary::Despawn })
+            .insert(Velocity {
+                x: 0.,
+                y: -POWER_UP_FALL_SPEED,
+            });
+    }
+}
+
+fn power_up_collect_system(
+    mut commands: Commands,
+    mut player_state: ResMut<PlayerState>,
+    mut events: EventReader<CollisionEvent>,
+    mut player_query: Query<&mut Health, With<Player>>,
+    power_up_query: Query<&PowerUp>,
+    enemy_laser_query: Query<Entity, (With<Laser>, With<FromEnemy>)>,
+) {
+    for event in events.iter() {
+        // el jugador puede haber sido destruido en este tick
+        if !event.is(layer::PLAYER, layer::POWER_UP) || !player_state.on {
+            continue;
+        }
+
+        let (Ok(mut health), Ok(power_up)) =
+            (player_query.get_mut(event.entity), power_up_query.get(event.other))
+        else {
+            continue;
+        };
+
+        commands.entity(event.other).despawn();
+
+        match power_up.0 {
+            PowerUpKind::Weapon => player_state.weapon = player_state.weapon.upgrade(),
+            PowerUpKind::Shield => *health = Health::new(PLAYER_HEALTH),
+            PowerUpKind::ExtraLife => {
+                player_state.lives = (player_state.lives + 1).min(PLAYER_MAX_LIVES)
+            }
+            PowerUpKind::Bomb => {
+                for laser_entity in enemy_laser_query.iter() {
+                    commands.entity(laser_entity).despawn();
+                }
+            }
+            PowerUpKind::SpeedBoost => player_state.speed_boost = PLAYER_SPEED_BOOST_TIME,
+        }
+    }
+}
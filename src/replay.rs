@@ -0,0 +1,180 @@
+use crate::rng::{rng_start_run_system, GameRng};
+use crate::state::GameState;
+use crate::{PlayerInput, PlayerState, SimulationSet, PLAYER_LIVES};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const REPLAY_VERSION: u32 = 4; // 3: generador ChaCha8, 4: vidas iniciales
+
+/// Grabación de una partida: la semilla, las vidas iniciales y la entrada del jugador en cada tick
+pub struct Replay {
+    pub seed: u64,
+    pub lives: u32,
+    pub inputs: Vec<u8>, // un valor de `PlayerInput::to_bits` por tick
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            lives: PLAYER_LIVES,
+            inputs: Vec::new(),
+        }
+    }
+}
+
+/// Formato del fichero de replay (entradas comprimidas como pares (entrada, repeticiones))
+#[derive(Serialize, Deserialize)]
+struct ReplayFile {
+    version: u32,
+    seed: u64,
+    lives: u32,
+    inputs: Vec<(u8, u32)>,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let file: ReplayFile = ron::from_str(&fs::read_to_string(path)?)?;
+        if file.version != REPLAY_VERSION {
+            return Err(format!("versión de replay no soportada: {}", file.version).into());
+        }
+
+        let inputs = file
+            .inputs
+            .into_iter()
+            .flat_map(|(bits, count)| std::iter::repeat_n(bits, count as usize))
+            .collect();
+
+        Ok(Self {
+            seed: file.seed,
+            lives: file.lives,
+            inputs,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut inputs: Vec<(u8, u32)> = Vec::new();
+        for &bits in self.inputs.iter() {
+            match inputs.last_mut() {
+                Some((last, count)) if *last == bits => *count += 1,
+                _ => inputs.push((bits, 1)),
+            }
+        }
+
+        let file = ReplayFile {
+            version: REPLAY_VERSION,
+            seed: self.seed,
+            lives: self.lives,
+            inputs,
+        };
+        fs::write(path, ron::to_string(&file)?)?;
+        Ok(())
+    }
+}
+
+impl ReplayMode {
+    /// guardar la grabación en curso, si la hay; se vacía para no guardarla dos veces
+    pub fn save_recording(&mut self) {
+        if let ReplayMode::Record { path, replay } = self {
+            if replay.inputs.is_empty() {
+                return;
+            }
+
+            match replay.save(path) {
+                Ok(()) => info!("replay guardado en {}", path.display()),
+                Err(err) => error!("no se pudo guardar el replay en {}: {err}", path.display()),
+            }
+            replay.inputs.clear();
+        }
+    }
+}
+
+/// Resource - Modo de grabación o reproducción de la partida
+#[derive(Resource, Default)]
+pub enum ReplayMode {
+    #[default]
+    Off,
+    Record {
+        path: PathBuf,
+        replay: Replay,
+    },
+    Playback {
+        replay: Replay,
+        cursor: usize,
+    },
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayMode>()
+            .add_systems(
+                OnExit(GameState::Menu),
+                replay_start_system.after(rng_start_run_system),
+            )
+            .add_systems(OnEnter(GameState::GameOver), replay_save_system)
+            .add_systems(OnEnter(GameState::Menu), replay_save_system)
+            // al cerrar la ventana o pulsar Esc en mitad de la partida
+            .add_systems(Last, replay_save_on_exit_system.run_if(on_event::<AppExit>()))
+            .add_systems(
+                FixedUpdate,
+                replay_input_system.in_set(SimulationSet::Prepare),
+            );
+    }
+}
+
+// preparar la grabación o la reproducción al empezar una partida
+fn replay_start_system(
+    mut replay_mode: ResMut<ReplayMode>,
+    mut player_state: ResMut<PlayerState>,
+    game_rng: Res<GameRng>,
+) {
+    match replay_mode.as_mut() {
+        ReplayMode::Off => {}
+        ReplayMode::Record { replay, .. } => {
+            replay.seed = game_rng.seed();
+            replay.lives = player_state.lives;
+            replay.inputs.clear();
+        }
+        // las vidas iniciales cambian la partida: se usan las de la grabación
+        ReplayMode::Playback { replay, cursor } => {
+            *player_state = PlayerState::new(replay.lives);
+            *cursor = 0;
+        }
+    }
+}
+
+// grabar o sustituir la entrada del jugador para este tick
+fn replay_input_system(
+    mut replay_mode: ResMut<ReplayMode>,
+    mut player_input: ResMut<PlayerInput>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    match replay_mode.as_mut() {
+        ReplayMode::Off => {}
+        ReplayMode::Record { replay, .. } => replay.inputs.push(player_input.to_bits()),
+        ReplayMode::Playback { replay, cursor } => match replay.inputs.get(*cursor) {
+            Some(&bits) => {
+                *player_input = PlayerInput::from_bits(bits);
+                *cursor += 1;
+            }
+            // fin de la grabación
+            None => next_state.set(GameState::GameOver),
+        },
+    }
+}
+
+// guardar la grabación al terminar la partida
+fn replay_save_system(mut replay_mode: ResMut<ReplayMode>) {
+    replay_mode.save_recording();
+}
+
+fn replay_save_on_exit_system(mut replay_mode: ResMut<ReplayMode>) {
+    replay_mode.save_recording();
+}
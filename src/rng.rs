@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+use rand::{thread_rng, Error, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Resource - Generador aleatorio de la partida
+///
+/// Toda la lógica de spawn y disparo debe usar este recurso en lugar de `thread_rng`,
+/// así la misma semilla con las mismas entradas reproduce la misma partida. Se usa un
+/// algoritmo con nombre (ChaCha8) y no `StdRng`, que puede cambiar entre versiones de
+/// `rand` y romper los replays compartidos.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    fixed_seed: bool, // si es false se elige una semilla nueva en cada partida
+    rng: ChaCha8Rng,
+}
+
+impl GameRng {
+    pub fn new(seed: Option<u64>) -> Self {
+        let fixed_seed = seed.is_some();
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+
+        Self {
+            seed,
+            fixed_seed,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// reiniciar el generador al empezar una partida
+    pub fn start_run(&mut self) {
+        if !self.fixed_seed {
+            self.seed = thread_rng().gen();
+        }
+        self.rng = ChaCha8Rng::seed_from_u64(self.seed);
+        info!("semilla de la partida: {}", self.seed);
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+// reiniciar la semilla al salir del menú (inicio de una partida nueva)
+pub fn rng_start_run_system(mut game_rng: ResMut<GameRng>) {
+    game_rng.start_run();
+}
@@ -0,0 +1,163 @@
+use crate::{Scoreboard, SCOREBOARD_FONT_SIZE, SCORE_COLOR, TEXT_COLOR};
+
+use bevy::prelude::*;
+
+const TITLE_FONT_SIZE: f32 = 90.;
+const OVERLAY_COLOR: Color = Color::rgba(0., 0., 0., 0.6);
+
+/// State - Estado global del juego
+#[derive(States, Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum GameState {
+    #[default]
+    Loading,
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/// Resource - Empezar la partida sin esperar al jugador en el menú (replays y headless)
+#[derive(Resource, Default)]
+pub struct AutoStart(pub bool);
+
+// region:      --- Screen Components ---
+
+#[derive(Component)]
+struct OnLoadingScreen;
+
+#[derive(Component)]
+struct OnMenuScreen;
+
+#[derive(Component)]
+struct OnPauseScreen;
+
+#[derive(Component)]
+struct OnGameOverScreen;
+
+// endregion:   --- Screen Components ---
+
+pub struct GameStatePlugin;
+
+impl Plugin for GameStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<GameState>()
+            .init_resource::<AutoStart>()
+            .add_systems(OnEnter(GameState::Loading), loading_setup_system)
+            .add_systems(OnExit(GameState::Loading), despawn_with::<OnLoadingScreen>)
+            .add_systems(OnEnter(GameState::Menu), menu_setup_system)
+            .add_systems(OnExit(GameState::Menu), despawn_with::<OnMenuScreen>)
+            .add_systems(OnEnter(GameState::Paused), pause_setup_system)
+            .add_systems(OnExit(GameState::Paused), (despawn_with::<OnPauseScreen>, unpause_system))
+            .add_systems(OnEnter(GameState::GameOver), game_over_setup_system)
+            .add_systems(OnExit(GameState::GameOver), despawn_with::<OnGameOverScreen>)
+            .add_systems(Update, menu_input_system.run_if(in_state(GameState::Menu)))
+            .add_systems(Update, playing_input_system.run_if(in_state(GameState::Playing)))
+            .add_systems(Update, pause_input_system.run_if(in_state(GameState::Paused)))
+            .add_systems(Update, game_over_input_system.run_if(in_state(GameState::GameOver)));
+    }
+}
+
+/// despawnear todas las entidades con el componente `T` (pantallas y entidades de juego)
+pub fn despawn_with<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// region:      --- Screens ---
+
+/// crear una pantalla centrada con un título y un subtítulo
+fn spawn_screen(commands: &mut Commands, marker: impl Component, title: &str, subtitle: &str) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: OVERLAY_COLOR.into(),
+            ..default()
+        })
+        .insert(marker)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                title,
+                TextStyle {
+                    font_size: TITLE_FONT_SIZE,
+                    color: SCORE_COLOR,
+                    ..default()
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                subtitle,
+                TextStyle {
+                    font_size: SCOREBOARD_FONT_SIZE,
+                    color: TEXT_COLOR,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn loading_setup_system(mut commands: Commands) {
+    spawn_screen(&mut commands, OnLoadingScreen, "Loading...", "");
+}
+
+fn menu_setup_system(mut commands: Commands) {
+    spawn_screen(&mut commands, OnMenuScreen, "Spade Invaders!", "Press ENTER to start");
+}
+
+fn pause_setup_system(mut commands: Commands, mut time: ResMut<Time>) {
+    // detener el reloj para que los temporizadores no avancen durante la pausa
+    time.pause();
+    spawn_screen(&mut commands, OnPauseScreen, "Paused", "P to resume - Q to quit");
+}
+
+fn unpause_system(mut time: ResMut<Time>) {
+    time.unpause();
+}
+
+fn game_over_setup_system(mut commands: Commands, scoreboard: Res<Scoreboard>) {
+    let subtitle = format!("Final score: {} - Press ENTER", scoreboard.score);
+    spawn_screen(&mut commands, OnGameOverScreen, "Game Over", &subtitle);
+}
+
+// endregion:   --- Screens ---
+
+// region:      --- Input ---
+
+fn menu_input_system(
+    kb: Res<Input<KeyCode>>,
+    auto_start: Res<AutoStart>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if auto_start.0 || kb.just_pressed(KeyCode::Return) {
+        next_state.set(GameState::Playing);
+    }
+}
+
+fn playing_input_system(kb: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if kb.just_pressed(KeyCode::P) {
+        next_state.set(GameState::Paused);
+    }
+}
+
+fn pause_input_system(kb: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if kb.just_pressed(KeyCode::P) {
+        next_state.set(GameState::Playing);
+    } else if kb.just_pressed(KeyCode::Q) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn game_over_input_system(kb: Res<Input<KeyCode>>, mut next_state: ResMut<NextState<GameState>>) {
+    if kb.just_pressed(KeyCode::Return) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+// endregion:   --- Input ---
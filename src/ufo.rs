@@ -0,0 +1,210 @@
+use crate::collision::{layer, Collider, CollisionEvent};
+use crate::components::{Boundary, ExplosionToSpawn, FromPlayer, Interpolated, Laser, Movable, SpriteSize, Velocity};
+use crate::rng::GameRng;
+use crate::state::{despawn_with, GameState};
+use crate::{
+    CollisionSet, GameTextures, LogicSet, Playfield, Scoreboard, UfoSound, ENEMY_SIZE,
+    SCOREBOARD_FONT_SIZE, SCORE_COLOR, TIME_STEP,
+};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+// segundos entre dos apariciones del platillo
+const UFO_INTERVAL: (f32, f32) = (20., 35.);
+// puntuaciones posibles al derribarlo
+const UFO_SCORES: [u32; 4] = [5, 10, 15, 30];
+const UFO_SCALE: f32 = 0.4;
+const UFO_COLOR: Color = Color::rgb(1., 0.3, 0.9);
+// velocidad horizontal, relativa a BASE_SPEED
+const UFO_SPEED: f32 = 0.4;
+// distancia del platillo al borde superior de la pantalla
+const UFO_TOP_MARGIN: f32 = 60.;
+const UFO_POPUP_TIME: f32 = 1.;
+const UFO_POPUP_RISE: f32 = 60.; // unidades por segundo
+
+/// Component - Platillo volante que cruza la parte superior de la pantalla
+#[derive(Component)]
+pub struct Ufo {
+    pub score: u32,
+}
+
+/// Component - Texto con la puntuación obtenida, desaparece tras un tiempo
+#[derive(Component)]
+pub struct ScorePopup {
+    time_left: f32,
+}
+
+/// Resource - Temporizador del platillo, independiente de las oleadas
+#[derive(Resource, Default)]
+pub struct UfoState {
+    next_spawn: Option<f32>, // segundos hasta la siguiente aparición
+}
+
+/// Plugin - Platillo volante de bonificación
+pub struct UfoPlugin;
+
+impl Plugin for UfoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UfoState>()
+            .add_systems(FixedUpdate, ufo_spawn_system.in_set(LogicSet::Ufo))
+            .add_systems(FixedUpdate, ufo_hit_system.in_set(CollisionSet::Ufo))
+            .add_systems(
+                Update,
+                score_popup_system.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::Paused), ufo_sound_pause_system)
+            .add_systems(OnExit(GameState::Paused), ufo_sound_resume_system)
+            // sin esto el platillo queda congelado y su sonido sigue bajo la pantalla final
+            .add_systems(OnEnter(GameState::GameOver), despawn_with::<Ufo>)
+            .add_systems(
+                OnEnter(GameState::Menu),
+                (
+                    despawn_with::<Ufo>,
+                    despawn_with::<ScorePopup>,
+                    ufo_reset_system,
+                ),
+            );
+    }
+}
+
+fn ufo_reset_system(mut ufo_state: ResMut<UfoState>) {
+    *ufo_state = UfoState::default();
+}
+
+// el sonido va en bucle: sin esto seguiría sonando con la pantalla de pausa
+fn ufo_sound_pause_system(query: Query<&AudioSink, With<Ufo>>) {
+    for sink in query.iter() {
+        sink.pause();
+    }
+}
+
+fn ufo_sound_resume_system(query: Query<&AudioSink, With<Ufo>>) {
+    for sink in query.iter() {
+        sink.play();
+    }
+}
+
+fn ufo_spawn_system(
+    mut commands: Commands,
+    mut ufo_state: ResMut<UfoState>,
+    mut rng: ResMut<GameRng>,
+    playfield: Res<Playfield>,
+    game_textures: Res<GameTextures>,
+    sound: Option<Res<UfoSound>>, // no existe en modo headless
+    query: Query<(), With<Ufo>>,
+) {
+    // el temporizador solo corre mientras no haya un platillo en pantalla
+    if !query.is_empty() {
+        return;
+    }
+
+    let time_left = match ufo_state.next_spawn {
+        Some(time_left) => time_left - TIME_STEP,
+        None => rng.gen_range(UFO_INTERVAL.0..UFO_INTERVAL.1),
+    };
+    if time_left > 0. {
+        ufo_state.next_spawn = Some(time_left);
+        return;
+    }
+    ufo_state.next_spawn = None;
+
+    // entrar por un lado al azar, justo fuera de la pantalla
+    let dir = if rng.gen_bool(0.5) { 1. } else { -1. };
+    let x = -dir * (playfield.rect.max.x + ENEMY_SIZE.0 * UFO_SCALE);
+    let y = playfield.rect.max.y - UFO_TOP_MARGIN;
+    let translation = Vec3::new(x, y, 10.);
+    let score = UFO_SCORES[rng.gen_range(0..UFO_SCORES.len())];
+
+    let mut ufo = commands.spawn(SpriteBundle {
+        texture: game_textures.enemy.clone(),
+        sprite: Sprite {
+            color: UFO_COLOR,
+            ..Default::default()
+        },
+        transform: Transform {
+            translation,
+            scale: Vec3::new(UFO_SCALE, UFO_SCALE, 1.),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    ufo.insert(Ufo { score })
+        .insert(Interpolated::from(translation))
+        .insert(SpriteSize::from(ENEMY_SIZE))
+        .insert(Collider::new(layer::UFO, layer::PLAYER_LASER))
+        .insert(Movable { boundary: Boundary::Despawn })
+        .insert(Velocity {
+            x: dir * UFO_SPEED,
+            y: 0.,
+        });
+
+    // el sonido vive en la entidad del platillo y se detiene al despawnearlo
+    if let Some(sound) = sound.as_ref() {
+        ufo.insert(AudioBundle {
+            source: sound.0.clone(),
+            settings: PlaybackSettings::LOOP,
+        });
+    }
+}
+
+fn ufo_hit_system(
+    mut commands: Commands,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut events: EventReader<CollisionEvent>,
+    laser_query: Query<(), (With<Laser>, With<FromPlayer>)>,
+    ufo_query: Query<(&Transform, &Ufo)>,
+    mut despawned_ufos: Local<Vec<Entity>>,
+) {
+    despawned_ufos.clear();
+
+    for event in events.iter() {
+        if !event.is(layer::UFO, layer::PLAYER_LASER) || despawned_ufos.contains(&event.entity) {
+            continue;
+        }
+
+        let (Ok((ufo_tf, ufo)), Ok(())) = (ufo_query.get(event.entity), laser_query.get(event.other))
+        else {
+            continue;
+        };
+
+        commands.entity(event.other).despawn();
+        commands.entity(event.entity).despawn();
+        despawned_ufos.push(event.entity);
+        commands.spawn(ExplosionToSpawn(ufo_tf.translation));
+        scoreboard.score += ufo.score as usize;
+
+        // mostrar la puntuación donde estaba el platillo
+        commands
+            .spawn(Text2dBundle {
+                text: Text::from_section(
+                    ufo.score.to_string(),
+                    TextStyle {
+                        font_size: SCOREBOARD_FONT_SIZE,
+                        color: SCORE_COLOR,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(ufo_tf.translation),
+                ..default()
+            })
+            .insert(ScorePopup {
+                time_left: UFO_POPUP_TIME,
+            });
+    }
+}
+
+fn score_popup_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut ScorePopup)>,
+) {
+    for (entity, mut transform, mut popup) in query.iter_mut() {
+        popup.time_left -= time.delta_seconds();
+        transform.translation.y += UFO_POPUP_RISE * time.delta_seconds();
+
+        if popup.time_left <= 0. {
+            commands.entity(entity).despawn();
+        }
+    }
+}
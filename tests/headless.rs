@@ -0,0 +1,192 @@
+//! Partidas completas sin ventana: misma semilla y misma entrada, misma partida
+
+use juego::components::{Enemy, Laser};
+use juego::enemy::wave::WaveState;
+use juego::headless::{headless_app, run_ticks, HeadlessSummary};
+use juego::level::{CurrentLevel, FormationDef, Level};
+use juego::replay::{Replay, ReplayMode};
+use juego::rng::GameRng;
+use juego::state::GameState;
+use juego::{EnemyCount, PlayerInput, PlayerState, SimulationTime, StartingLives};
+
+use bevy::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+const SEED: u64 = 3;
+const TICKS: u64 = 2000;
+const LEVEL_FILE: &str = "assets/levels/level_01.level.ron";
+
+// barrer la pantalla de lado a lado disparando sin parar
+fn sweep_inputs() -> Vec<u8> {
+    (0..TICKS)
+        .map(|tick| {
+            let left = (tick / 60) % 2 == 0;
+            PlayerInput {
+                left,
+                right: !left,
+                fire_held: true,
+                ..Default::default()
+            }
+            .to_bits()
+        })
+        .collect()
+}
+
+/// Resultado de una partida: el resumen final y una huella de las posiciones de
+/// enemigos y lasers en cada tick, que cambia aunque el resumen coincida
+#[derive(Debug, PartialEq)]
+struct Run {
+    summary: HeadlessSummary,
+    lives_lost: u32,
+    trace: u64,
+}
+
+fn play(replay: Replay) -> Run {
+    let game_rng = GameRng::new(Some(replay.seed));
+    let mut app = headless_app(game_rng, ReplayMode::Playback { replay, cursor: 0 });
+    run_ticks(&mut app, 0); // esperar a que empiece la partida
+
+    let mut query = app
+        .world
+        .query_filtered::<&Transform, Or<(With<Enemy>, With<Laser>)>>();
+    let mut hasher = DefaultHasher::new();
+    let mut positions = Vec::new();
+    let mut lives_lost = 0;
+    while app.world.resource::<SimulationTime>().tick < TICKS
+        && *app.world.resource::<State<GameState>>().get() != GameState::GameOver
+    {
+        let lives = app.world.resource::<PlayerState>().lives;
+        app.update();
+        if app.world.resource::<PlayerState>().lives < lives {
+            lives_lost += 1;
+        }
+
+        // el orden de las consultas no forma parte de la partida
+        positions.clear();
+        positions.extend(
+            query
+                .iter(&app.world)
+                .map(|tf| (tf.translation.x.to_bits(), tf.translation.y.to_bits())),
+        );
+        positions.sort_unstable();
+        positions.hash(&mut hasher);
+    }
+
+    Run {
+        summary: run_ticks(&mut app, TICKS),
+        lives_lost,
+        trace: hasher.finish(),
+    }
+}
+
+#[test]
+fn same_seed_and_inputs_give_the_same_run() {
+    let first = play(Replay {
+        seed: SEED,
+        inputs: sweep_inputs(),
+        ..Default::default()
+    });
+    let second = play(Replay {
+        seed: SEED,
+        inputs: sweep_inputs(),
+        ..Default::default()
+    });
+
+    // la partida tiene que hacer algo: puntuar y perder alguna vida
+    assert!(first.summary.score > 0, "no se ha puntuado: {}", first.summary);
+    assert!(first.lives_lost > 0, "no se ha perdido ninguna vida: {}", first.summary);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn saved_replay_plays_back_the_same_run() {
+    let path = std::env::temp_dir().join(format!("juego-test-{}.replay.ron", std::process::id()));
+    let replay = Replay {
+        seed: SEED,
+        inputs: sweep_inputs(),
+        ..Default::default()
+    };
+    replay.save(&path).expect("guardar el replay");
+    let loaded = Replay::load(&path).expect("cargar el replay");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.seed, replay.seed);
+    assert_eq!(loaded.lives, replay.lives);
+    assert_eq!(loaded.inputs, replay.inputs);
+    assert_eq!(play(loaded), play(replay));
+}
+
+#[test]
+fn starting_lives_are_recorded_and_played_back() {
+    let path = std::env::temp_dir().join(format!("juego-test-lives-{}.replay.ron", std::process::id()));
+    let mut app = headless_app(
+        GameRng::new(Some(SEED)),
+        ReplayMode::Record {
+            path: path.clone(),
+            replay: Replay::default(),
+        },
+    );
+    app.insert_resource(StartingLives(5));
+    let recorded = run_ticks(&mut app, 60);
+    let replay = Replay::load(&path).expect("cargar el replay");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(recorded.lives, 5);
+    assert_eq!(replay.lives, 5);
+    // la App usa las vidas por defecto, pero la reproducción toma las del replay
+    let mut app = headless_app(GameRng::new(Some(SEED)), ReplayMode::Playback { replay, cursor: 0 });
+    assert_eq!(run_ticks(&mut app, 60).lives, 5);
+}
+
+#[test]
+fn grid_landing_ends_the_game() {
+    let mut app = headless_app(GameRng::new(Some(SEED)), ReplayMode::Off);
+    run_ticks(&mut app, 1);
+
+    // saltar a la oleada en rejilla, sin disparos ni picados para que el jugador no muera
+    let mut level = app.world.resource_mut::<CurrentLevel>();
+    let grid = level
+        .0
+        .waves
+        .iter()
+        .position(|wave| matches!(wave.formation, FormationDef::Grid { .. }))
+        .expect("el nivel no tiene oleada en rejilla");
+    level.0.waves[grid].dive = None;
+    if let FormationDef::Grid { speed, max_speed, .. } = &mut level.0.waves[grid].formation {
+        *speed = *max_speed; // llegar abajo en pocos segundos
+    }
+    for archetype in level.0.archetypes.values_mut() {
+        archetype.weapon = None;
+    }
+
+    let enemies: Vec<Entity> = app
+        .world
+        .query_filtered::<Entity, With<Enemy>>()
+        .iter(&app.world)
+        .collect();
+    for entity in enemies {
+        app.world.despawn(entity);
+    }
+    app.world.resource_mut::<EnemyCount>().0 = 0;
+    *app.world.resource_mut::<WaveState>() = WaveState {
+        wave: grid,
+        ..Default::default()
+    };
+
+    let summary = run_ticks(&mut app, 60 * 60 * 5);
+    assert!(summary.game_over, "la rejilla no ha aterrizado: {summary}");
+    assert!(summary.lives > 0, "la partida ha terminado por vidas: {summary}");
+}
+
+#[test]
+fn level_file_parses_and_validates() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(LEVEL_FILE);
+    let source = std::fs::read_to_string(path).expect("leer el nivel");
+    let level: Level = ron::from_str(&source).expect("el nivel no es RON válido");
+
+    assert!(!level.waves.is_empty());
+    if let Err(err) = level.validate() {
+        panic!("el nivel no pasa la validación: {err}");
+    }
+}